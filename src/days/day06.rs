@@ -0,0 +1,313 @@
+use crate::template::Day;
+use rayon::prelude::*;
+use std::str::FromStr;
+
+/// The day solved by this module.
+pub const DAY: Day = crate::day!(6);
+
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+enum Dir {
+    N,
+    W,
+    S,
+    E,
+}
+
+impl Dir {
+    fn turn_right(self) -> Dir {
+        match self {
+            Dir::N => Dir::E,
+            Dir::E => Dir::S,
+            Dir::S => Dir::W,
+            Dir::W => Dir::N,
+        }
+    }
+}
+
+/// Marks a jump leaving the area.
+const EXIT: u32 = u32::MAX;
+
+struct Area {
+    width: usize,
+    height: usize,
+    /// Cells indexed by `y * width + x`.
+    obstacles: Vec<bool>,
+    guard: usize,
+    gdir: Dir,
+    /// For each direction, the cell where the guard walking from a cell stops in front of an
+    /// obstacle, `EXIT` if it leaves the area.
+    jumps: [Vec<u32>; 4],
+}
+
+impl FromStr for Area {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<_> = s.lines().collect();
+        let height = lines.len();
+        let width = lines[0].len();
+
+        let mut obstacles = vec![false; width * height];
+        let mut guard = None;
+        for (y, l) in lines.iter().enumerate() {
+            for (x, c) in l.chars().enumerate() {
+                let gdir = match c {
+                    '#' => {
+                        obstacles[y * width + x] = true;
+                        continue;
+                    }
+                    '^' => Dir::N,
+                    '>' => Dir::E,
+                    '<' => Dir::W,
+                    'v' => Dir::S,
+                    _ => continue,
+                };
+                guard = Some((y * width + x, gdir));
+            }
+        }
+        let (guard, gdir) = guard.ok_or("no guard in the area")?;
+
+        let mut jumps: [Vec<u32>; 4] = std::array::from_fn(|_| vec![EXIT; width * height]);
+        for i in 0..width * height {
+            let (x, y) = (i % width, i / width);
+            if y > 0 {
+                jumps[Dir::N as usize][i] = if obstacles[i - width] {
+                    i as u32
+                } else {
+                    jumps[Dir::N as usize][i - width]
+                };
+            }
+            if x > 0 {
+                jumps[Dir::W as usize][i] = if obstacles[i - 1] {
+                    i as u32
+                } else {
+                    jumps[Dir::W as usize][i - 1]
+                };
+            }
+        }
+        for i in (0..width * height).rev() {
+            let (x, y) = (i % width, i / width);
+            if y + 1 < height {
+                jumps[Dir::S as usize][i] = if obstacles[i + width] {
+                    i as u32
+                } else {
+                    jumps[Dir::S as usize][i + width]
+                };
+            }
+            if x + 1 < width {
+                jumps[Dir::E as usize][i] = if obstacles[i + 1] {
+                    i as u32
+                } else {
+                    jumps[Dir::E as usize][i + 1]
+                };
+            }
+        }
+
+        Ok(Self {
+            width,
+            height,
+            obstacles,
+            guard,
+            gdir,
+            jumps,
+        })
+    }
+}
+
+impl Area {
+    /// The cell next to `pos` in direction `dir`, `None` out of the area.
+    fn step(&self, pos: usize, dir: Dir) -> Option<usize> {
+        let (x, y) = (pos % self.width, pos / self.width);
+        match dir {
+            Dir::N => (y > 0).then(|| pos - self.width),
+            Dir::W => (x > 0).then(|| pos - 1),
+            Dir::S => (y + 1 < self.height).then(|| pos + self.width),
+            Dir::E => (x + 1 < self.width).then(|| pos + 1),
+        }
+    }
+
+    /// The positions and directions of the guard, one move or turn at a time, until it leaves.
+    fn path(&self) -> Vec<(usize, Dir)> {
+        let (mut pos, mut dir) = (self.guard, self.gdir);
+        let mut path = vec![(pos, dir)];
+        while let Some(next) = self.step(pos, dir) {
+            if self.obstacles[next] {
+                dir = dir.turn_right();
+            } else {
+                pos = next;
+            }
+            path.push((pos, dir));
+        }
+        path
+    }
+
+    /// Whether the guard walking from `pos` towards `dir` loops once an obstacle is added on
+    /// `extra`. `seen` holds the turns already taken, those equal to `stamp` for this walk.
+    fn loops(
+        &self,
+        mut pos: usize,
+        mut dir: Dir,
+        extra: usize,
+        seen: &mut [u32],
+        stamp: u32,
+    ) -> bool {
+        let (ex, ey) = (extra % self.width, extra / self.width);
+        loop {
+            let (x, y) = (pos % self.width, pos / self.width);
+            // the cell in front of the extra obstacle, when the guard walks towards it.
+            let before_extra = match dir {
+                Dir::N => (ex == x && ey < y).then(|| extra + self.width),
+                Dir::S => (ex == x && ey > y).then(|| extra - self.width),
+                Dir::W => (ey == y && ex < x).then(|| extra + 1),
+                Dir::E => (ey == y && ex > x).then(|| extra - 1),
+            };
+            let jump = self.jumps[dir as usize][pos];
+            let distance = |cell: usize| cell.abs_diff(pos);
+
+            pos = match (jump, before_extra) {
+                (EXIT, None) => return false,
+                (EXIT, Some(before)) => before,
+                (jump, Some(before)) if distance(before) < distance(jump as usize) => before,
+                (jump, _) => jump as usize,
+            };
+            dir = dir.turn_right();
+
+            let turn = pos * 4 + dir as usize;
+            if seen[turn] == stamp {
+                return true;
+            }
+            seen[turn] = stamp;
+        }
+    }
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let area = Area::from_str(input).unwrap();
+
+    let mut visited = vec![false; area.obstacles.len()];
+    for (pos, _) in area.path() {
+        visited[pos] = true;
+    }
+
+    Some(visited.into_iter().filter(|v| *v).count())
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let area = Area::from_str(input).unwrap();
+
+    // an obstacle only changes the walk if it is on the original path, and it must be placed
+    // where the guard first enters its cell: the walk up to there is the original one.
+    let mut tried = vec![false; area.obstacles.len()];
+    tried[area.guard] = true;
+    let candidates: Vec<(usize, usize, Dir)> = area
+        .path()
+        .windows(2)
+        .filter_map(|w| {
+            let ((pos, dir), (next, _)) = (w[0], w[1]);
+            if next == pos || tried[next] {
+                return None;
+            }
+            tried[next] = true;
+            Some((next, pos, dir))
+        })
+        .collect();
+
+    let count = candidates
+        .par_iter()
+        .map_init(
+            || (vec![0; area.obstacles.len() * 4], 0),
+            |(seen, stamp), &(extra, pos, dir)| {
+                *stamp += 1;
+                area.loops(pos, dir, extra, seen, *stamp)
+            },
+        )
+        .filter(|loops| *loops)
+        .count();
+
+    Some(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Some(41));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Some(6));
+    }
+
+    /// Number of cells where an obstacle makes the guard loop, walking cell by cell from the start.
+    fn brute_force_loops(input: &str) -> usize {
+        let area = Area::from_str(input).unwrap();
+        (0..area.obstacles.len())
+            .filter(|i| !area.obstacles[*i] && *i != area.guard)
+            .filter(|extra| {
+                let mut seen = std::collections::HashSet::new();
+                let (mut pos, mut dir) = (area.guard, area.gdir);
+                while seen.insert((pos, dir)) {
+                    match area.step(pos, dir) {
+                        None => return false,
+                        Some(next) if area.obstacles[next] || next == *extra => {
+                            dir = dir.turn_right()
+                        }
+                        Some(next) => pos = next,
+                    }
+                }
+                true
+            })
+            .count()
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut state = 0x0612_u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..100 {
+            let (w, h) = (5 + next() as usize % 12, 5 + next() as usize % 12);
+            let guard = next() as usize % (w * h);
+            let guard_dir = ['^', '>', 'v', '<'][next() as usize % 4];
+            let input: String = (0..h)
+                .map(|y| {
+                    (0..w)
+                        .map(|x| match y * w + x {
+                            i if i == guard => guard_dir,
+                            _ if next() % 100 < 12 => '#',
+                            _ => '.',
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            // the original walk of the guard must leave the area.
+            let area = Area::from_str(&input).unwrap();
+            let mut seen = std::collections::HashSet::new();
+            let (mut pos, mut dir) = (area.guard, area.gdir);
+            let leaves = loop {
+                if !seen.insert((pos, dir)) {
+                    break false;
+                }
+                match area.step(pos, dir) {
+                    None => break true,
+                    Some(next) if area.obstacles[next] => dir = dir.turn_right(),
+                    Some(next) => pos = next,
+                }
+            };
+            if leaves {
+                assert_eq!(part_two(&input), Some(brute_force_loops(&input)), "{input}");
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use crate::template::{param, Day, ParamKind, Parameter};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::Write;
+
+/// The day solved by this module.
+pub const DAY: Day = crate::day!(9);
+
+pub const PARAMETERS: &[Parameter] = &[Parameter {
+    name: "trace",
+    default: "false",
+    kind: ParamKind::Flag,
+    description: "print the disk on stderr after each file moved by part two",
+}];
+
+type Block = Option<usize>;
+
+fn read_disk(input: &str) -> Vec<Block> {
+    let mut disk: Vec<Block> = Vec::with_capacity(input.len());
+    let mut fileid = 0;
+    let mut describing_file = true;
+    for val in input.trim().chars().map(|c| c.to_digit(10).unwrap()) {
+        if val == 0 && describing_file {
+            panic!("describing empty file {fileid} ?");
+        }
+        for _ in 0..val as usize {
+            disk.push(Some(fileid).filter(|_| describing_file));
+        }
+        if describing_file {
+            fileid += 1;
+        }
+        describing_file = !describing_file;
+    }
+    disk
+}
+
+/// The disk map, one character per block: the last digit of the file id, coloured after the
+/// whole id, or a dimmed `.` for a free block.
+pub fn render(disk: &[Block]) -> String {
+    let mut map = String::with_capacity(disk.len() * 12);
+    for b in disk {
+        match b {
+            // the colour cube of 256-colour terminals, without its black and white corners.
+            Some(id) => {
+                let _ = write!(map, "\x1b[38;5;{}m{}", 17 + id % 214, id % 10);
+            }
+            None => map.push_str("\x1b[2m.\x1b[22m"),
+        }
+    }
+    map.push_str("\x1b[0m");
+    map
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let disk = read_disk(input);
+
+    let mut blocks_it = disk.iter();
+    let mut rev_fblocks_it = disk.iter().enumerate().filter(|(_, b)| b.is_some()).rev();
+
+    let mut defragmented_d: Vec<Block> = Vec::with_capacity(disk.len());
+    let mut forward_id = 0;
+    let mut rev_id = disk.len() - 1;
+    while forward_id < rev_id {
+        forward_id += 1;
+        if let Some(Some(fileid)) = blocks_it.next() {
+            defragmented_d.push(Some(*fileid));
+        } else if let Some((j, b)) = rev_fblocks_it.next() {
+            rev_id = j;
+            if forward_id >= rev_id {
+                break;
+            }
+            defragmented_d.push(Some(b.unwrap()));
+        }
+    }
+
+    assert_eq!(
+        disk.iter().filter(|b| b.is_some()).count(),
+        defragmented_d.iter().filter(|b| b.is_some()).count()
+    );
+
+    Some(
+        defragmented_d
+            .iter()
+            .enumerate()
+            .map(|(i, b)| b.unwrap_or(0) * i)
+            .sum(),
+    )
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct File {
+    id: usize,
+    pos: usize,
+    len: usize,
+}
+
+/// The files of the disk by id, along with the free spans as `(position, length)`.
+fn read_spans(input: &str) -> (Vec<File>, Vec<(usize, usize)>) {
+    let mut files = Vec::with_capacity(input.len() / 2 + 1);
+    let mut free = Vec::with_capacity(input.len() / 2);
+    let mut pos = 0;
+    for (i, len) in input
+        .trim()
+        .chars()
+        .map(|c| c.to_digit(10).unwrap() as usize)
+        .enumerate()
+    {
+        if i % 2 == 0 {
+            if len == 0 {
+                panic!("describing empty file {} ?", files.len());
+            }
+            files.push(File {
+                id: files.len(),
+                pos,
+                len,
+            });
+        } else if len > 0 {
+            free.push((pos, len));
+        }
+        pos += len;
+    }
+    (files, free)
+}
+
+/// The blocks of the disk holding `files`.
+fn layout(files: &[File]) -> Vec<Block> {
+    let size = files.iter().map(|f| f.pos + f.len).max().unwrap_or(0);
+    let mut disk = vec![None; size];
+    for f in files {
+        disk[f.pos..f.pos + f.len].fill(Some(f.id));
+    }
+    disk
+}
+
+/// Moves each file, from the last one, to the leftmost free span before it that fits it.
+/// `on_move` is called with the files after each move.
+fn compact(files: &mut [File], free: Vec<(usize, usize)>, mut on_move: impl FnMut(&[File])) {
+    // the positions of the free spans, by length. A moved file leaves the rest of its span on the
+    // heap of the shorter length; the space it frees is right of every file left to move.
+    let mut heaps: [BinaryHeap<Reverse<usize>>; 10] = Default::default();
+    for (pos, len) in free {
+        heaps[len].push(Reverse(pos));
+    }
+
+    for id in (0..files.len()).rev() {
+        let file = files[id];
+        let Some((len, pos)) = (file.len..10)
+            .filter_map(|len| heaps[len].peek().map(|Reverse(pos)| (len, *pos)))
+            .filter(|(_, pos)| *pos < file.pos)
+            .min_by_key(|(_, pos)| *pos)
+        else {
+            continue;
+        };
+
+        heaps[len].pop();
+        if len > file.len {
+            heaps[len - file.len].push(Reverse(pos + file.len));
+        }
+        files[id].pos = pos;
+
+        on_move(files);
+    }
+}
+
+/// Prints the disk after each move of part 2 to stderr, if selected by the parameters.
+pub fn inspect(input: &str, part: u8) {
+    if part != 2 || !param("trace", false) {
+        return;
+    }
+    let (mut files, free) = read_spans(input);
+    compact(&mut files, free, |files| {
+        eprintln!("{}", render(&layout(files)))
+    });
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let (mut files, free) = read_spans(input);
+    compact(&mut files, free, |_| {});
+
+    Some(
+        files
+            .iter()
+            .map(|f| f.id * (f.pos * f.len + f.len * (f.len - 1) / 2))
+            .sum(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Some(1928));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Some(2858));
+    }
+
+    /// Part two moving blocks one at a time.
+    fn compact_blocks(input: &str) -> usize {
+        let mut disk = read_disk(input);
+        let last_file = disk.iter().flatten().max().copied().unwrap();
+        for id in (0..=last_file).rev() {
+            let start = disk.iter().position(|b| *b == Some(id)).unwrap();
+            let len = disk[start..].iter().take_while(|b| **b == Some(id)).count();
+            let Some(free) = (0..start).find(|i| disk[*i..*i + len].iter().all(Option::is_none))
+            else {
+                continue;
+            };
+            disk[start..start + len].fill(None);
+            disk[free..free + len].fill(Some(id));
+        }
+        disk.iter()
+            .enumerate()
+            .map(|(i, b)| i * b.unwrap_or(0))
+            .sum()
+    }
+
+    #[test]
+    fn matches_block_compaction() {
+        let mut state = 0x0909_u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..200 {
+            let len = 1 + next() as usize % 60;
+            let input: String = (0..len)
+                .map(|i| {
+                    let min = if i % 2 == 0 { 1 } else { 0 };
+                    char::from(b'0' + min + (next() % (10 - min as u64)) as u8)
+                })
+                .collect();
+            assert_eq!(part_two(&input), Some(compact_blocks(&input)), "{input}");
+        }
+    }
+
+    #[test]
+    fn renders_disk() {
+        let (files, _) = read_spans("12345");
+        assert_eq!(
+            render(&layout(&files)),
+            "\x1b[38;5;17m0\x1b[2m.\x1b[22m\x1b[2m.\x1b[22m\x1b[38;5;18m1\x1b[38;5;18m1\x1b[38;5;18m1\
+             \x1b[2m.\x1b[22m\x1b[2m.\x1b[22m\x1b[2m.\x1b[22m\x1b[2m.\x1b[22m\
+             \x1b[38;5;19m2\x1b[38;5;19m2\x1b[38;5;19m2\x1b[38;5;19m2\x1b[38;5;19m2\x1b[0m"
+        );
+    }
+}
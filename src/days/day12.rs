@@ -0,0 +1,366 @@
+use crate::template::{param, Day, ParamKind, Parameter};
+use crossterm::style::{Color, Stylize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+/// The day solved by this module.
+pub const DAY: Day = crate::day!(12);
+
+pub const PARAMETERS: &[Parameter] = &[
+    Parameter {
+        name: "report",
+        default: "false",
+        kind: ParamKind::Flag,
+        description: "print every region with its area, perimeter, sides and prices to stderr",
+    },
+    Parameter {
+        name: "render",
+        default: "false",
+        kind: ParamKind::Flag,
+        description: "print the garden with coloured regions and their borders to stderr",
+    },
+];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+struct Point {
+    x: isize,
+    y: isize,
+}
+
+impl Point {
+    fn neighbors(&self) -> [Point; 4] {
+        let &Point { x, y } = self;
+        [
+            Point { x: x - 1, y },
+            Point { x: x + 1, y },
+            Point { x, y: y - 1 },
+            Point { x, y: y + 1 },
+        ]
+    }
+}
+
+fn perimeter(area: &HashSet<Point>) -> usize {
+    area.iter()
+        .map(|&p| {
+            p.neighbors()
+                .into_iter()
+                .filter(|n| !area.contains(n))
+                .count()
+        })
+        .sum()
+}
+
+/// Number of sides of the region, counting each side on its first plot.
+fn sides(area: &HashSet<Point>) -> usize {
+    area.iter()
+        .map(|&Point { x, y }| {
+            let mut p = 0usize;
+            //left
+            if !area.contains(&Point { x: x - 1, y })
+                && (!area.contains(&Point { x, y: y - 1 })
+                    || area.contains(&Point { x: x - 1, y: y - 1 }))
+            {
+                p += 1
+            }
+            //right
+            if (!area.contains(&Point { x: x + 1, y }))
+                && (!area.contains(&Point { x, y: y - 1 })
+                    || area.contains(&Point { x: x + 1, y: y - 1 }))
+            {
+                p += 1
+            }
+
+            //up
+            if !area.contains(&Point { x, y: y - 1 })
+                && (!area.contains(&Point { x: x - 1, y })
+                    || area.contains(&Point { x: x - 1, y: y - 1 }))
+            {
+                p += 1
+            }
+            //down
+            if (!area.contains(&Point { x, y: y + 1 }))
+                && (!area.contains(&Point { x: x - 1, y })
+                    || area.contains(&Point { x: x - 1, y: y + 1 }))
+            {
+                p += 1
+            }
+
+            p
+        })
+        .sum()
+}
+
+/// A region of plots of the same plant, with its measures and prices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub plant: char,
+    /// The first plot of the region in reading order, as `(x, y)`.
+    pub origin: (usize, usize),
+    pub area: usize,
+    pub perimeter: usize,
+    pub sides: usize,
+}
+
+impl Region {
+    fn new(plant: char, plots: &HashSet<Point>) -> Self {
+        let origin = plots.iter().map(|p| (p.y, p.x)).min().unwrap();
+        Self {
+            plant,
+            origin: (origin.1 as usize, origin.0 as usize),
+            area: plots.len(),
+            perimeter: perimeter(plots),
+            sides: sides(plots),
+        }
+    }
+
+    /// Price of the fence, by area and perimeter.
+    pub fn price(&self) -> usize {
+        self.area * self.perimeter
+    }
+
+    /// Price of the fence with the bulk discount, by area and number of sides.
+    pub fn bulk_price(&self) -> usize {
+        self.area * self.sides
+    }
+}
+
+impl Display for Region {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:>9} {:>5} {:>9} {:>5} {:>7} {:>7}",
+            self.plant,
+            format!("{},{}", self.origin.0, self.origin.1),
+            self.area,
+            self.perimeter,
+            self.sides,
+            self.price(),
+            self.bulk_price()
+        )
+    }
+}
+
+/// The regions of the garden, in reading order of their first plot.
+pub fn regions(input: &str) -> Vec<Region> {
+    let mut regions: Vec<Region> = collect_areas(input)
+        .iter()
+        .map(|(plant, plots)| Region::new(*plant, plots))
+        .collect();
+    regions.sort_by_key(|r| (r.origin.1, r.origin.0));
+    regions
+}
+
+/// The regions with a header line and the total prices, one region per line.
+pub fn report(regions: &[Region]) -> String {
+    let mut text = String::from("plant  origin  area perimeter sides   price    bulk\n");
+    for region in regions {
+        text.push_str(&format!("{region}\n"));
+    }
+    text.push_str(&format!(
+        "total {:>35} {:>7}\n",
+        regions.iter().map(Region::price).sum::<usize>(),
+        regions.iter().map(Region::bulk_price).sum::<usize>()
+    ));
+    text
+}
+
+/// The garden with the borders of its regions drawn between plots, each region in a colour of
+/// its own when `color` is set.
+pub fn render(input: &str, color: bool) -> String {
+    let plants: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+    let (w, h) = (plants[0].len(), plants.len());
+
+    let mut region_of = vec![vec![0; w]; h];
+    for (i, (_, plots)) in collect_areas(input).iter().enumerate() {
+        for p in plots {
+            region_of[p.y as usize][p.x as usize] = i + 1;
+        }
+    }
+    // region of a plot, 0 out of the garden.
+    let region = |x: isize, y: isize| {
+        if x < 0 || y < 0 || x >= w as isize || y >= h as isize {
+            0
+        } else {
+            region_of[y as usize][x as usize]
+        }
+    };
+    // borders are on odd columns of even rows and even columns of odd rows.
+    let border = |gx: isize, gy: isize| {
+        if gx < 0 || gy < 0 || gx > 2 * w as isize || gy > 2 * h as isize {
+            return false;
+        }
+        let (x, y) = (gx / 2, gy / 2);
+        if gy % 2 == 1 {
+            region(x - 1, y) != region(x, y)
+        } else {
+            region(x, y - 1) != region(x, y)
+        }
+    };
+    const CORNERS: [char; 16] = [
+        ' ', '╵', '╷', '│', '╴', '┘', '┐', '┤', '╶', '└', '┌', '├', '─', '┴', '┬', '┼',
+    ];
+
+    let mut text = String::new();
+    for gy in 0..=2 * h as isize {
+        for gx in 0..=2 * w as isize {
+            match (gx % 2, gy % 2) {
+                (0, 0) => {
+                    let corner = border(gx, gy - 1) as usize
+                        | (border(gx, gy + 1) as usize) << 1
+                        | (border(gx - 1, gy) as usize) << 2
+                        | (border(gx + 1, gy) as usize) << 3;
+                    text.push(CORNERS[corner]);
+                }
+                (1, 1) => {
+                    let (x, y) = (gx as usize / 2, gy as usize / 2);
+                    let plant = plants[y][x];
+                    if color {
+                        let r = region_of[y][x];
+                        let shade = Color::AnsiValue(17 + (r * 37 % 214) as u8);
+                        text.push_str(&plant.with(shade).to_string());
+                    } else {
+                        text.push(plant);
+                    }
+                }
+                (0, _) if border(gx, gy) => text.push('│'),
+                (_, 0) if border(gx, gy) => text.push('─'),
+                _ => text.push(' '),
+            }
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// The regions of the garden, with their plant.
+fn collect_areas(input: &str) -> Vec<(char, HashSet<Point>)> {
+    let mut plots: HashMap<Point, char> = input
+        .lines()
+        .enumerate()
+        .flat_map(|(y, l)| {
+            l.chars().enumerate().map(move |(x, sort)| {
+                (
+                    Point {
+                        x: x as isize,
+                        y: y as isize,
+                    },
+                    sort,
+                )
+            })
+        })
+        .collect();
+
+    let mut areas: Vec<(char, HashSet<Point>)> = vec![];
+    let mut current_sort = plots.remove(&Point { x: 0, y: 0 }).unwrap();
+    let mut current_area: HashSet<Point> = HashSet::new();
+    current_area.insert(Point { x: 0, y: 0 });
+    while !plots.is_empty() {
+        let mut neighbors: Vec<Point> = vec![];
+
+        for p in &current_area {
+            for neighbour in p
+                .neighbors()
+                .iter()
+                .filter(|n| plots.get(*n).map(|c| *c == current_sort).unwrap_or(false))
+            {
+                neighbors.push(*neighbour)
+            }
+        }
+
+        for n in &neighbors {
+            plots.remove(n);
+        }
+        let no_new_points = neighbors.is_empty();
+        current_area.extend(neighbors);
+
+        if no_new_points {
+            areas.push((current_sort, current_area.clone()));
+
+            if let Some((p, c)) = plots.iter().next() {
+                current_area = HashSet::new();
+                let p = *p;
+                current_area.insert(p);
+                current_sort = *c;
+                plots.remove(&p);
+            }
+        }
+    }
+    if !current_area.is_empty() {
+        areas.push((current_sort, current_area));
+    }
+
+    areas
+}
+
+/// Prints the report or the rendering of the regions of part 1 to stderr, as selected by the
+/// parameters.
+pub fn inspect(input: &str, part: u8) {
+    if part != 1 {
+        return;
+    }
+    if param("report", false) {
+        eprint!("{}", report(&regions(input)));
+    }
+    if param("render", false) {
+        eprint!("{}", render(input, true));
+    }
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    Some(regions(input).iter().map(Region::price).sum())
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    Some(regions(input).iter().map(Region::bulk_price).sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Some(1930));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Some(1206));
+    }
+
+    #[test]
+    fn reports_regions() {
+        let regions = regions("AAAA\nBBCD\nBBCC\nEEEC");
+        let measures: Vec<_> = regions
+            .iter()
+            .map(|r| (r.plant, r.origin, r.area, r.perimeter, r.sides))
+            .collect();
+        assert_eq!(
+            measures,
+            [
+                ('A', (0, 0), 4, 10, 4),
+                ('B', (0, 1), 4, 8, 4),
+                ('C', (2, 1), 4, 10, 8),
+                ('D', (3, 1), 1, 4, 4),
+                ('E', (0, 3), 3, 8, 4)
+            ]
+        );
+        assert_eq!(
+            report(&regions[..2]),
+            "plant  origin  area perimeter sides   price    bulk\n\
+             A       0,0     4        10     4      40      16\n\
+             B       0,1     4         8     4      32      16\n\
+             total                                  72      32\n"
+        );
+    }
+
+    #[test]
+    fn renders_borders() {
+        assert_eq!(
+            render("AB\nAA", false),
+            "┌─┬─┐\n│A│B│\n│ └─┤\n│A A│\n└───┘\n"
+        );
+    }
+}
@@ -0,0 +1,397 @@
+use crate::template::{param, Day, ParamKind, Parameter};
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+
+/// The day solved by this module.
+pub const DAY: Day = crate::day!(13);
+
+pub const PARAMETERS: &[Parameter] = &[Parameter {
+    name: "report",
+    default: "false",
+    kind: ParamKind::Flag,
+    description: "print the presses and tokens of every machine to stderr",
+}];
+
+/// Offset of the prizes in part 2, on both axes.
+const PRIZE_OFFSET: i64 = 10_000_000_000_000;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Button {
+    pub cost: i64,
+    pub mv: Point,
+}
+
+fn read_button(line: &str) -> Button {
+    debug_assert!(line.starts_with("Button "), "{line}");
+    let cost = match line.as_bytes()["Button ".len()] {
+        b'A' => 3,
+        b'B' => 1,
+        _ => unreachable!("Unknown button"),
+    };
+    let x = line[line.find("X+").unwrap() + 2..]
+        .split(',')
+        .next()
+        .unwrap()
+        .parse::<i64>()
+        .unwrap();
+    let y = line[line.find("Y+").unwrap() + 2..]
+        .trim()
+        .parse::<i64>()
+        .unwrap();
+    let mv = Point { x, y };
+    Button { cost, mv }
+}
+fn read_prize(line: &str) -> Point {
+    debug_assert!(line.starts_with("Prize:"));
+
+    let x = line[line.find("X=").unwrap() + 2..]
+        .split(',')
+        .next()
+        .unwrap()
+        .parse::<i64>()
+        .unwrap();
+    let y = line[line.find("Y=").unwrap() + 2..]
+        .trim()
+        .parse::<i64>()
+        .unwrap();
+    Point { x, y }
+}
+
+/// Number of presses of each button winning a prize, with their cost in tokens.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Presses {
+    pub a: i64,
+    pub b: i64,
+    pub cost: i64,
+}
+
+/// Error returned when solving a machine does not fit in 64-bit integers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct OverflowError {
+    pub prize: Point,
+}
+
+impl Display for OverflowError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "arithmetic overflow on the machine with the prize at X={}, Y={}",
+            self.prize.x, self.prize.y
+        )
+    }
+}
+
+/// Solutions `(x, y, g)` of `a * x + b * y = g`, `g` being the greatest common divisor.
+fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if b == 0 {
+        (1, 0, a)
+    } else {
+        let (x, y, g) = extended_gcd(b, a % b);
+        (y, x - (a / b) * y, g)
+    }
+}
+
+/// Smallest integer at least `a / b`, for a positive `b`.
+fn div_ceil(a: i64, b: i64) -> i64 {
+    -(-a).div_euclid(b)
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Game {
+    pub a: Button,
+    pub b: Button,
+    pub prize: Point,
+    /// Most presses of each button, `None` when unlimited.
+    pub max_push: Option<i64>,
+}
+
+impl Game {
+    /// The cheapest presses winning the prize, `None` if it cannot be won.
+    pub fn solve(&self) -> Result<Option<Presses>, OverflowError> {
+        let overflow = OverflowError { prize: self.prize };
+        let (a, b) = (self.a.mv, self.b.mv);
+
+        let det = (a.x.checked_mul(b.y))
+            .zip(b.x.checked_mul(a.y))
+            .and_then(|(l, r)| l.checked_sub(r))
+            .ok_or(overflow)?;
+        let presses = if det != 0 {
+            self.solve_unique(det).ok_or(overflow)?
+        } else {
+            self.solve_collinear().ok_or(overflow)?
+        };
+
+        presses
+            .filter(|(na, nb)| {
+                let max = self.max_push.unwrap_or(i64::MAX);
+                (0..=max).contains(na) && (0..=max).contains(nb)
+            })
+            .map(|(na, nb)| {
+                let cost = na
+                    .checked_mul(self.a.cost)
+                    .zip(nb.checked_mul(self.b.cost))
+                    .and_then(|(ca, cb)| ca.checked_add(cb))
+                    .ok_or(overflow)?;
+                Ok(Presses { a: na, b: nb, cost })
+            })
+            .transpose()
+    }
+
+    /// The single solution of the equations when the moves are independent, `None` on overflow.
+    fn solve_unique(&self, det: i64) -> Option<Option<(i64, i64)>> {
+        let (a, b, p) = (self.a.mv, self.b.mv, self.prize);
+        let na = p.x.checked_mul(b.y)?.checked_sub(p.y.checked_mul(b.x)?)?;
+        let nb = a.x.checked_mul(p.y)?.checked_sub(a.y.checked_mul(p.x)?)?;
+        if na % det != 0 || nb % det != 0 {
+            return Some(None);
+        }
+        Some(Some((na / det, nb / det)))
+    }
+
+    /// The cheapest solution when both moves go along the same line, `None` on overflow.
+    ///
+    /// Along that line, the solutions of `a * na + b * nb = p` are
+    /// `(na0 + k * b / g, nb0 - k * a / g)` out of the extended GCD. The cost is linear in `k`,
+    /// so the cheapest solution is at either end of the range of `k` keeping the presses valid.
+    fn solve_collinear(&self) -> Option<Option<(i64, i64)>> {
+        let (a, b, p) = (self.a.mv, self.b.mv, self.prize);
+        let zero = Point { x: 0, y: 0 };
+        let only = |m: Point| {
+            if m == zero {
+                return Some((p == zero).then_some(0));
+            }
+            let n = if m.x != 0 { p.x / m.x } else { p.y / m.y };
+            Some((m.x.checked_mul(n)? == p.x && m.y.checked_mul(n)? == p.y).then_some(n))
+        };
+        // a button which does not move is never worth pressing.
+        match (a == zero, b == zero) {
+            (true, _) => return Some(only(b)?.map(|nb| (0, nb))),
+            (false, true) => return Some(only(a)?.map(|na| (na, 0))),
+            _ => {}
+        }
+
+        // the prize must be on the line too, then one axis is enough.
+        if p.x.checked_mul(a.y)? != p.y.checked_mul(a.x)? {
+            return Some(None);
+        }
+        let (ca, cb, c) = if a.x != 0 {
+            (a.x, b.x, p.x)
+        } else {
+            (a.y, b.y, p.y)
+        };
+
+        let (u, v, g) = extended_gcd(ca, cb);
+        if c % g != 0 {
+            return Some(None);
+        }
+        let (na0, nb0) = (u.checked_mul(c / g)?, v.checked_mul(c / g)?);
+        let (step_a, step_b) = (cb / g, ca / g);
+
+        let mut low = div_ceil(na0.checked_neg()?, step_a);
+        let mut high = nb0.div_euclid(step_b);
+        if let Some(max) = self.max_push {
+            low = low.max(div_ceil(nb0.checked_sub(max)?, step_b));
+            high = high.min(max.checked_sub(na0)?.div_euclid(step_a));
+        }
+        if low > high {
+            return Some(None);
+        }
+
+        // pressing A once more along the family saves tokens when it replaces enough B presses.
+        let k = if self.a.cost.checked_mul(step_a)? < self.b.cost.checked_mul(step_b)? {
+            high
+        } else {
+            low
+        };
+        Some(Some((
+            na0.checked_add(k.checked_mul(step_a)?)?,
+            nb0.checked_sub(k.checked_mul(step_b)?)?,
+        )))
+    }
+}
+
+fn read_games(input: &str, offset: i64, max_push: Option<i64>) -> Result<Vec<Game>, OverflowError> {
+    input
+        .lines()
+        .chunks(4)
+        .into_iter()
+        .map(|mut v| {
+            let a = read_button(v.next().unwrap());
+            let b = read_button(v.next().unwrap());
+            let prize = read_prize(v.next().unwrap());
+            let moved = Point {
+                x: prize.x.checked_add(offset).ok_or(OverflowError { prize })?,
+                y: prize.y.checked_add(offset).ok_or(OverflowError { prize })?,
+            };
+            Ok(Game {
+                a,
+                b,
+                prize: moved,
+                max_push,
+            })
+        })
+        .collect()
+}
+
+/// Tokens spent on winning every prize which can be won.
+fn total_cost(games: &[Game]) -> Result<i64, OverflowError> {
+    let mut total: i64 = 0;
+    for game in games {
+        if let Some(presses) = game.solve()? {
+            total = total
+                .checked_add(presses.cost)
+                .ok_or(OverflowError { prize: game.prize })?;
+        }
+    }
+    Ok(total)
+}
+
+/// The games of `part`.
+fn part_games(input: &str, part: u8) -> Result<Vec<Game>, OverflowError> {
+    match part {
+        1 => read_games(input, 0, Some(100)),
+        _ => read_games(input, PRIZE_OFFSET, None),
+    }
+}
+
+/// Prints the presses of every machine to stderr, if selected by the parameters.
+pub fn inspect(input: &str, part: u8) {
+    if !param("report", false) {
+        return;
+    }
+    let Ok(games) = part_games(input, part) else {
+        return;
+    };
+    for (i, game) in games.iter().enumerate() {
+        match game.solve() {
+            Ok(Some(Presses { a, b, cost })) => {
+                eprintln!("machine {}: {a} A + {b} B = {cost} tokens", i + 1)
+            }
+            Ok(None) => eprintln!("machine {}: no win", i + 1),
+            Err(e) => eprintln!("machine {}: {e}", i + 1),
+        }
+    }
+}
+
+pub fn part_one(input: &str) -> Result<usize, OverflowError> {
+    let cost = total_cost(&part_games(input, 1)?)?;
+    Ok(cost as usize)
+}
+
+pub fn part_two(input: &str) -> Result<usize, OverflowError> {
+    let cost = total_cost(&part_games(input, 2)?)?;
+    Ok(cost as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Ok(480));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Ok(875318608908));
+    }
+
+    fn game(a: (i64, i64), b: (i64, i64), prize: (i64, i64)) -> Game {
+        Game {
+            a: Button {
+                cost: 3,
+                mv: Point { x: a.0, y: a.1 },
+            },
+            b: Button {
+                cost: 1,
+                mv: Point { x: b.0, y: b.1 },
+            },
+            prize: Point {
+                x: prize.0,
+                y: prize.1,
+            },
+            max_push: Some(100),
+        }
+    }
+
+    #[test]
+    fn solves_collinear_buttons() {
+        let cost = |g: Game| g.solve().unwrap().map(|p| (p.a, p.b, p.cost));
+        assert_eq!(cost(game((2, 2), (1, 1), (10, 10))), Some((0, 10, 10)));
+        assert_eq!(cost(game((4, 4), (1, 1), (10, 10))), Some((2, 2, 8)));
+        assert_eq!(cost(game((2, 2), (4, 4), (5, 5))), None);
+        assert_eq!(cost(game((1, 1), (2, 2), (3, 4))), None);
+        assert_eq!(cost(game((0, 0), (3, 1), (6, 2))), Some((0, 2, 2)));
+        assert_eq!(cost(game((1, 0), (2, 0), (400, 0))), None);
+
+        // without limit on the presses, as in part 2.
+        let unlimited = |g: Game| Game {
+            max_push: None,
+            ..g
+        };
+        assert_eq!(
+            cost(unlimited(game((4, 4), (6, 6), (10, 10)))),
+            Some((1, 1, 4))
+        );
+        assert_eq!(
+            cost(unlimited(game((1, 0), (2, 0), (400, 0)))),
+            Some((0, 200, 200))
+        );
+        assert_eq!(
+            cost(unlimited(game(
+                (3, 3),
+                (2, 2),
+                (10_000_000_000_000, 10_000_000_000_000)
+            ))),
+            Some((0, 5_000_000_000_000, 5_000_000_000_000))
+        );
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut state = 0x1313_u64;
+        let mut next = |n: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % n) as i64
+        };
+        for _ in 0..500 {
+            let a = (next(6), next(6));
+            // collinear moves half of the time.
+            let b = if next(2) == 0 {
+                let (m, d) = (1 + next(4), 1 + next(3));
+                (a.0 * m / d, a.1 * m / d)
+            } else {
+                (next(6), next(6))
+            };
+            let prize = (next(200), next(200));
+            let game = game(a, b, prize);
+
+            let expected = (0..=100)
+                .cartesian_product(0..=100)
+                .filter(|(na, nb)| na * a.0 + nb * b.0 == prize.0 && na * a.1 + nb * b.1 == prize.1)
+                .map(|(na, nb)| 3 * na + nb)
+                .min();
+            let cost = game.solve().unwrap().map(|p| p.cost);
+            assert_eq!(cost, expected, "{game:?}");
+        }
+    }
+
+    #[test]
+    fn reports_overflow() {
+        let input =
+            "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=9223372036854775000, Y=5400\n";
+        assert!(read_games(input, PRIZE_OFFSET, None).is_err());
+        assert!(part_two(input).is_err());
+    }
+}
@@ -0,0 +1,433 @@
+use crate::template::{param, viewer, Day, ParamKind, Parameter};
+use crossterm::style::Stylize;
+use itertools::Itertools;
+use rustc_hash::FxHashSet;
+use std::str::FromStr;
+
+/// The day solved by this module.
+pub const DAY: Day = crate::day!(14);
+
+pub struct Robot {
+    x: isize,
+    y: isize,
+    vx: isize,
+    vy: isize,
+}
+
+impl Robot {
+    pub fn new(l: &str) -> Robot {
+        let (p, v) = l.split_whitespace().collect_tuple().unwrap();
+        assert!(p.starts_with("p="));
+        assert!(v.starts_with("v="));
+        let (x, y) = p
+            .split(['=', ','])
+            .skip(1)
+            .map(|c| c.trim().parse::<isize>().unwrap())
+            .collect_tuple()
+            .unwrap();
+        let (vx, vy) = v
+            .split(['=', ','])
+            .skip(1)
+            .map(|cv| cv.trim().parse::<isize>().unwrap())
+            .collect_tuple()
+            .unwrap();
+        Self { x, y, vx, vy }
+    }
+    pub fn pos_after(&self, times: isize, w: isize, h: isize) -> (isize, isize) {
+        let &Self { x, y, vx, vy } = self;
+        (
+            (w + (x + times * vx) % w) % w,
+            (h + (y + times * vy) % h) % h,
+        )
+    }
+}
+
+pub fn positions_after(robots: &[Robot], times: isize, w: isize, h: isize) -> Vec<(isize, isize)> {
+    robots.iter().map(|r| r.pos_after(times, w, h)).collect()
+}
+
+pub fn safety_factor(input: &str, times: isize, w: isize, h: isize) -> isize {
+    let robots: Vec<Robot> = input.lines().map(Robot::new).collect();
+    quadrants_product(&positions_after(&robots, times, w, h), w, h)
+}
+
+/// Product of the number of robots in each quadrant, robots on the middle lines are not counted.
+pub fn quadrants_product(pos: &[(isize, isize)], w: isize, h: isize) -> isize {
+    let (nw, ne, sw, se) = pos.iter().fold((0, 0, 0, 0), |(nw, ne, sw, se), (x, y)| {
+        let (x, y) = (*x, *y);
+        (
+            nw + if x < w / 2 && y < h / 2 { 1 } else { 0 },
+            ne + if x > w / 2 && y < h / 2 { 1 } else { 0 },
+            sw + if x < w / 2 && y > h / 2 { 1 } else { 0 },
+            se + if x > w / 2 && y > h / 2 { 1 } else { 0 },
+        )
+    });
+    nw * ne * sw * se
+}
+
+const W: isize = 101;
+const H: isize = 103;
+
+pub const PARAMETERS: &[Parameter] = &[
+    Parameter {
+        name: "w",
+        default: "101",
+        kind: ParamKind::Count,
+        description: "width of the area",
+    },
+    Parameter {
+        name: "h",
+        default: "103",
+        kind: ParamKind::Count,
+        description: "height of the area",
+    },
+    Parameter {
+        name: "visualize",
+        default: "false",
+        kind: ParamKind::Flag,
+        description: "browse the robots over time in part 2, starting from the tree found",
+    },
+    Parameter {
+        name: "detector",
+        default: "triangle",
+        kind: ParamKind::OneOf(&["triangle", "variance"]),
+        description:
+            "how part 2 finds the tree: `triangle` of robots or `variance` minima per axis",
+    },
+    Parameter {
+        name: "report",
+        default: "false",
+        kind: ParamKind::Flag,
+        description:
+            "print the candidate times of the variance detector with their scores to stderr",
+    },
+];
+
+pub fn part_one(input: &str) -> Option<usize> {
+    Some(safety_factor(input, 100, param("w", W), param("h", H)) as usize)
+}
+
+// To be lowered if no image comes out
+const EDGE_LEN: usize = 4;
+
+pub fn maybe_christmas_tree(pos: &[(isize, isize)]) -> bool {
+    tree_score(pos) >= EDGE_LEN
+}
+
+/// Edge length of the largest triangle of robots, a hint of how much the area looks like a tree.
+pub fn tree_score(pos: &[(isize, isize)]) -> usize {
+    // let's look for some \ edges ?
+    let pos: FxHashSet<(isize, isize)> = pos.iter().copied().collect();
+
+    // looking for    #
+    // a triangle    # #
+    // shape        #   #
+    pos.iter()
+        .map(|(x, y)| {
+            (1..)
+                .take_while(|i| pos.contains(&(*x + i, *y + i)) && pos.contains(&(*x - i, *y + i)))
+                .count()
+                + 1
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// How the time of the tree is searched.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Detector {
+    /// First time a triangle of `EDGE_LEN` robots shows up.
+    Triangle,
+    /// Time where robots gather the most along both axes.
+    Variance,
+}
+
+impl FromStr for Detector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "triangle" => Ok(Detector::Triangle),
+            "variance" => Ok(Detector::Variance),
+            _ => Err(format!("expecting `triangle` or `variance`, got `{s}`")),
+        }
+    }
+}
+
+/// Dispersion of the robots along one axis, `offset` seconds into the period of the axis.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AxisScore {
+    pub offset: isize,
+    pub variance: f64,
+    /// Shannon entropy of the coordinates, in bits.
+    pub entropy: f64,
+}
+
+impl AxisScore {
+    fn new(offset: isize, coords: &[isize]) -> Self {
+        let n = coords.len() as f64;
+        let mean = coords.iter().sum::<isize>() as f64 / n;
+        let variance = coords
+            .iter()
+            .map(|c| (*c as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        let entropy = coords
+            .iter()
+            .counts()
+            .values()
+            .map(|count| {
+                let p = *count as f64 / n;
+                -p * p.log2()
+            })
+            .sum();
+        Self {
+            offset,
+            variance,
+            entropy,
+        }
+    }
+}
+
+/// Scores of every offset along one axis of size `len`, lowest variance first.
+fn axis_scores(
+    robots: &[Robot],
+    len: isize,
+    axis: impl Fn(&Robot) -> (isize, isize),
+) -> Vec<AxisScore> {
+    (0..len)
+        .map(|t| {
+            let coords: Vec<isize> = robots
+                .iter()
+                .map(|r| {
+                    let (p, v) = axis(r);
+                    (p + t * v).rem_euclid(len)
+                })
+                .collect();
+            AxisScore::new(t, &coords)
+        })
+        .sorted_by(|a, b| a.variance.total_cmp(&b.variance))
+        .collect()
+}
+
+/// Smallest non-negative `t` such that `t = a1 [n1]` and `t = a2 [n2]`, if any.
+pub fn crt(a1: isize, n1: isize, a2: isize, n2: isize) -> Option<isize> {
+    fn extended_gcd(a: isize, b: isize) -> (isize, isize, isize) {
+        if b == 0 {
+            (a, 1, 0)
+        } else {
+            let (g, x, y) = extended_gcd(b, a % b);
+            (g, y, x - a / b * y)
+        }
+    }
+
+    let (g, p, _) = extended_gcd(n1, n2);
+    if (a2 - a1) % g != 0 {
+        return None;
+    }
+    let lcm = n1 / g * n2;
+    // n1 * p = g [n2], so a1 + n1 * p * (a2 - a1) / g solves both.
+    let k = ((a2 - a1) / g % (n2 / g) * p).rem_euclid(n2 / g);
+    Some((a1 + n1 * k).rem_euclid(lcm))
+}
+
+/// A time at which the robots may draw a tree, along with the scores of both axes at that time.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Candidate {
+    pub time: isize,
+    pub x: AxisScore,
+    pub y: AxisScore,
+}
+
+/// Combines the `k` offsets of lowest variance along each axis into times, as positions along `x`
+/// repeat every `w` seconds and along `y` every `h` seconds. Best candidates first.
+pub fn tree_candidates(robots: &[Robot], w: isize, h: isize, k: usize) -> Vec<Candidate> {
+    let xs = axis_scores(robots, w, |r| (r.x, r.vx));
+    let ys = axis_scores(robots, h, |r| (r.y, r.vy));
+
+    xs.iter()
+        .take(k)
+        .cartesian_product(ys.iter().take(k))
+        .filter_map(|(x, y)| {
+            let time = crt(x.offset, w, y.offset, h)?;
+            Some(Candidate { time, x: *x, y: *y })
+        })
+        .sorted_by(|a, b| (a.x.variance + a.y.variance).total_cmp(&(b.x.variance + b.y.variance)))
+        .collect()
+}
+
+/// Draws the area with two rows of cells per line of text.
+pub fn render(pos: &[(isize, isize)], w: isize, h: isize) -> String {
+    let pos: FxHashSet<(isize, isize)> = pos.iter().copied().collect();
+    (0..h)
+        .step_by(2)
+        .map(|y| {
+            let line: String = (0..w)
+                .map(
+                    |x| match (pos.contains(&(x, y)), pos.contains(&(x, y + 1))) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    },
+                )
+                .collect();
+            line.dark_green().to_string()
+        })
+        .join("\n")
+}
+
+/// Browses the positions of the robots over time in the terminal, starting at time `start`.
+pub fn visualize(robots: &[Robot], w: isize, h: isize, start: isize) {
+    let frames = (w * h) as usize;
+    let shown = viewer::play(frames, start as usize, |t| {
+        let pos = positions_after(robots, t as isize, w, h);
+        format!(
+            "time: {t}  safety factor: {}  tree score: {}\n{}",
+            quadrants_product(&pos, w, h),
+            tree_score(&pos),
+            render(&pos, w, h)
+        )
+    });
+    if let Err(e) = shown {
+        eprintln!("could not draw to the terminal: {e}");
+    }
+}
+
+/// Finds the first time the robots draw a Christmas tree, with the detector of the parameters.
+fn find_tree(robots: &[Robot], w: isize, h: isize) -> Option<isize> {
+    match param("detector", Detector::Triangle) {
+        // positions repeat after w * h seconds.
+        Detector::Triangle => {
+            (0..w * h).find(|t| maybe_christmas_tree(&positions_after(robots, *t, w, h)))
+        }
+        Detector::Variance => tree_candidates(robots, w, h, 3).first().map(|c| c.time),
+    }
+}
+
+/// Prints the candidates of the variance detector or opens the viewer on the tree found by part
+/// 2, as selected by the parameters.
+pub fn inspect(input: &str, part: u8) {
+    if part != 2 {
+        return;
+    }
+    let robots: Vec<Robot> = input.lines().map(Robot::new).collect();
+    let (w, h) = (param("w", W), param("h", H));
+
+    if param("report", false) && param("detector", Detector::Triangle) == Detector::Variance {
+        eprintln!("time    var x    var y  entropy");
+        for c in &tree_candidates(&robots, w, h, 3) {
+            eprintln!(
+                "{:>5} {:>8.1} {:>8.1} {:>8.3}",
+                c.time,
+                c.x.variance,
+                c.y.variance,
+                c.x.entropy + c.y.entropy
+            );
+        }
+    }
+
+    if param("visualize", false) {
+        visualize(&robots, w, h, find_tree(&robots, w, h).unwrap_or(0));
+    }
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let robots: Vec<Robot> = input.lines().map(Robot::new).collect();
+    let (w, h) = (param("w", W), param("h", H));
+
+    find_tree(&robots, w, h).map(|t| t as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::with_params;
+
+    #[test]
+    fn test_part_one() {
+        let result = with_params(&[("w", "11"), ("h", "7")], || {
+            part_one(&crate::template::read_file("examples", DAY))
+        });
+        assert_eq!(result, Some(12));
+    }
+
+    #[test]
+    fn scores_trees() {
+        let tree = [(5, 0), (4, 1), (6, 1), (3, 2), (7, 2), (2, 3), (8, 3)];
+        assert_eq!(tree_score(&tree), 4);
+        assert!(maybe_christmas_tree(&tree));
+        assert_eq!(tree_score(&tree[..5]), 3);
+        assert!(!maybe_christmas_tree(&tree[..5]));
+        assert_eq!(tree_score(&[]), 0);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt(2, 3, 3, 5), Some(8));
+        assert_eq!(crt(0, 101, 0, 103), Some(0));
+        assert_eq!(crt(100, 101, 102, 103), Some(101 * 103 - 1));
+        assert_eq!(crt(1, 4, 3, 6), Some(9));
+        assert_eq!(crt(1, 4, 2, 6), None);
+    }
+
+    /// Robots scattered over a 101x103 area which all gather in a 10x10 square at time `at`.
+    fn gathering_robots(at: isize) -> String {
+        let mut state = 7_u64;
+        let mut next = |n: isize| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) as isize % n
+        };
+        (0..300)
+            .map(|_| {
+                let (x, y) = (45 + next(10), 40 + next(10));
+                let (vx, vy) = (next(201) - 100, next(201) - 100);
+                let (x0, y0) = ((x - at * vx).rem_euclid(W), (y - at * vy).rem_euclid(H));
+                format!("p={x0},{y0} v={vx},{vy}")
+            })
+            .join("\n")
+    }
+
+    #[test]
+    fn detects_gathering_by_variance() {
+        let input = gathering_robots(6_543);
+        let result = with_params(&[("detector", "variance")], || part_two(&input));
+        assert_eq!(result, Some(6_543));
+
+        let robots: Vec<Robot> = input.lines().map(Robot::new).collect();
+        let candidates = tree_candidates(&robots, W, H, 3);
+        assert_eq!(candidates.len(), 9);
+        assert_eq!(
+            (candidates[0].x.offset, candidates[0].y.offset),
+            (6_543 % W, 6_543 % H)
+        );
+        // gathered robots also have the lowest entropy.
+        let best = candidates[0];
+        assert!(candidates
+            .iter()
+            .all(|c| c.x.entropy >= best.x.entropy && c.y.entropy >= best.y.entropy));
+    }
+
+    #[test]
+    fn renders_two_rows_per_line() {
+        let text = render(&[(0, 0), (1, 1), (2, 0), (2, 1)], 4, 3);
+        let text = String::from_utf8(strip_ansi(text.as_bytes())).unwrap();
+        assert_eq!(text, "▀▄█ \n    ");
+    }
+
+    fn strip_ansi(bytes: &[u8]) -> Vec<u8> {
+        let mut out = vec![];
+        let mut escaped = false;
+        for &b in bytes {
+            match b {
+                0x1b => escaped = true,
+                b'm' if escaped => escaped = false,
+                _ if escaped => {}
+                _ => out.push(b),
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,505 @@
+use crate::template::{param, viewer, Day, ParamKind, Parameter};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter, Write};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tinyjson::JsonValue;
+
+/// The day solved by this module.
+pub const DAY: Day = crate::day!(15);
+
+pub const PARAMETERS: &[Parameter] = &[
+    Parameter {
+        name: "visualize",
+        default: "false",
+        kind: ParamKind::Flag,
+        description: "replay the moves of the robot in the terminal",
+    },
+    Parameter {
+        name: "cast",
+        default: "",
+        kind: ParamKind::Text,
+        description:
+            "path to export the moves as an asciinema cast, suffixed by the part: `moves-1.cast`",
+    },
+];
+
+/// Delay between two moves in exported casts, in seconds.
+const CAST_MOVE_DELAY: f64 = 0.05;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl Direction {
+    fn step(self, (x, y): (isize, isize)) -> (isize, isize) {
+        match self {
+            Direction::Left => (x - 1, y),
+            Direction::Right => (x + 1, y),
+            Direction::Up => (x, y - 1),
+            Direction::Down => (x, y + 1),
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_char(match self {
+            Direction::Left => '<',
+            Direction::Right => '>',
+            Direction::Up => '^',
+            Direction::Down => 'v',
+        })
+    }
+}
+
+/// A move of the robot, enough to replay it or undo it on the warehouse.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Move {
+    pub direction: Direction,
+    /// Position of the robot before the move.
+    pub robot: (isize, isize),
+    /// Boxes pushed, by their position before the move. Empty when blocked.
+    pub pushed: Vec<(isize, isize)>,
+    /// The robot or a box it pushes hit a wall, nothing moved.
+    pub blocked: bool,
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (x, y) = self.robot;
+        write!(f, "{} from ({x},{y})", self.direction)?;
+        match self.pushed.len() {
+            _ if self.blocked => f.write_str(", blocked"),
+            0 => Ok(()),
+            1 => f.write_str(", pushed 1 box"),
+            n => write!(f, ", pushed {n} boxes"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct WareHouse<const WIDE: bool> {
+    boxes: HashSet<(isize, isize)>,
+    walls: HashSet<(isize, isize)>,
+    robot: (isize, isize),
+    instrs: Vec<Direction>,
+}
+
+impl<const WIDE: bool> WareHouse<WIDE> {
+    pub fn read(input: &str) -> Self {
+        let mut boxes: HashSet<(isize, isize)> = HashSet::new();
+        let mut walls: HashSet<(isize, isize)> = HashSet::new();
+        let mut robot: (isize, isize) = (0, 0);
+        let mut instrs: Vec<Direction> = vec![];
+        let w_factor = if WIDE { 2 } else { 1 };
+
+        for (i, j, c) in input
+            .lines()
+            .enumerate()
+            .flat_map(|(j, l)| l.chars().enumerate().map(move |(i, c)| (i, j, c)))
+        {
+            match c {
+                '#' => {
+                    walls.insert((w_factor * i as isize, j as isize));
+                    if WIDE {
+                        walls.insert((w_factor * i as isize + 1, j as isize));
+                    }
+                }
+                '@' => {
+                    robot = (w_factor * i as isize, j as isize);
+                }
+                'O' => {
+                    boxes.insert((w_factor * i as isize, j as isize));
+                }
+                '<' => {
+                    instrs.push(Direction::Left);
+                }
+                '>' => {
+                    instrs.push(Direction::Right);
+                }
+                '^' => {
+                    instrs.push(Direction::Up);
+                }
+                'v' => {
+                    instrs.push(Direction::Down);
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            boxes,
+            walls,
+            robot,
+            instrs,
+        }
+    }
+
+    pub fn gps(&self) -> usize {
+        self.boxes
+            .iter()
+            .map(|&(i, j)| i as usize + 100 * j as usize)
+            .sum()
+    }
+
+    fn tick_narrow(&self, d: Direction) -> Move {
+        let mut pushed = vec![];
+        let mut pos = d.step(self.robot);
+
+        while self.boxes.contains(&pos) {
+            pushed.push(pos);
+            pos = d.step(pos);
+        }
+        let blocked = self.walls.contains(&pos);
+        if blocked {
+            pushed.clear();
+        }
+
+        Move {
+            direction: d,
+            robot: self.robot,
+            pushed,
+            blocked,
+        }
+    }
+
+    fn tick_wide(&self, d: Direction) -> Move {
+        let blocked = |pushed| Move {
+            direction: d,
+            robot: self.robot,
+            pushed,
+            blocked: true,
+        };
+
+        let mut moved_boxes: Vec<(isize, isize)> = vec![];
+        let mut front: HashSet<(isize, isize)> = HashSet::new();
+        front.insert(d.step(self.robot));
+
+        loop {
+            if front.iter().any(|p| self.walls.contains(p)) {
+                return blocked(vec![]);
+            }
+
+            let new_moved_boxes: HashSet<_> = front
+                .iter()
+                .filter_map(|&(x, y)| self.boxes.get(&(x, y)).or(self.boxes.get(&(x - 1, y))))
+                .filter(|nmb| !moved_boxes.contains(nmb))
+                .cloned()
+                .collect();
+
+            if new_moved_boxes.is_empty() {
+                break;
+            }
+            front = new_moved_boxes
+                .iter()
+                .flat_map(move |&(x, y)| [d.step((x, y)), d.step((x + 1, y))].into_iter())
+                .collect();
+            moved_boxes.extend(new_moved_boxes);
+        }
+
+        Move {
+            direction: d,
+            robot: self.robot,
+            pushed: moved_boxes,
+            blocked: false,
+        }
+    }
+
+    /// Plays a move, computed or recorded.
+    pub fn apply(&mut self, m: &Move) {
+        if m.blocked {
+            return;
+        }
+        self.robot = m.direction.step(m.robot);
+        for b in &m.pushed {
+            self.boxes.remove(b);
+        }
+        for b in &m.pushed {
+            self.boxes.insert(m.direction.step(*b));
+        }
+    }
+
+    /// Reverts a move, which must be the last one applied.
+    pub fn undo(&mut self, m: &Move) {
+        if m.blocked {
+            return;
+        }
+        self.robot = m.robot;
+        for b in &m.pushed {
+            self.boxes.remove(&m.direction.step(*b));
+        }
+        for b in &m.pushed {
+            self.boxes.insert(*b);
+        }
+    }
+
+    /// Runs all the instructions, returns the log of the moves.
+    pub fn compute(&mut self) -> Vec<Move> {
+        let instrs = self.instrs.clone();
+        instrs
+            .into_iter()
+            .map(|d| {
+                let m = if WIDE {
+                    self.tick_wide(d)
+                } else {
+                    self.tick_narrow(d)
+                };
+                self.apply(&m);
+                m
+            })
+            .collect()
+    }
+
+    /// Width and height of the warehouse.
+    pub fn size(&self) -> (isize, isize) {
+        self.walls
+            .iter()
+            .fold((0, 0), |(w, h), (x, y)| (w.max(x + 1), h.max(y + 1)))
+    }
+
+    fn cell(&self, (i, j): (isize, isize)) -> char {
+        if self.walls.contains(&(i, j)) {
+            '#'
+        } else if self.boxes.contains(&(i, j)) {
+            if WIDE {
+                '['
+            } else {
+                'O'
+            }
+        } else if WIDE && self.boxes.contains(&(i - 1, j)) {
+            ']'
+        } else if self.robot == (i, j) {
+            '@'
+        } else {
+            '.'
+        }
+    }
+
+    /// Exports the moves played from this warehouse as an asciinema v2 cast: the map is drawn
+    /// once, then each move only redraws the cells it changed along with a status line.
+    pub fn to_cast(&self, log: &[Move]) -> String {
+        let (w, h) = self.size();
+        let event = |time: f64, data: String| {
+            let data = JsonValue::String(data).stringify().unwrap();
+            format!("[{time:.3}, \"o\", {data}]\n")
+        };
+
+        let mut cast = format!(
+            "{{\"version\": 2, \"width\": {}, \"height\": {}}}\n",
+            w.max(40),
+            h + 1
+        );
+        cast.push_str(&event(
+            0.0,
+            format!("\x1b[2J\x1b[H{}", self.to_string().replace('\n', "\r\n")),
+        ));
+
+        let mut warehouse = self.clone();
+        for (i, m) in log.iter().enumerate() {
+            warehouse.apply(m);
+
+            let mut changed = vec![m.robot, m.direction.step(m.robot)];
+            for b in &m.pushed {
+                let moved = m.direction.step(*b);
+                changed.extend([*b, moved]);
+                if WIDE {
+                    changed.extend([(b.0 + 1, b.1), (moved.0 + 1, moved.1)]);
+                }
+            }
+
+            let mut data = String::new();
+            for (x, y) in changed {
+                let _ = write!(data, "\x1b[{};{}H{}", y + 1, x + 1, warehouse.cell((x, y)));
+            }
+            let _ = write!(
+                data,
+                "\x1b[{};1H\x1b[2Kmove {}/{}: {m}",
+                h + 1,
+                i + 1,
+                log.len()
+            );
+            cast.push_str(&event((i + 1) as f64 * CAST_MOVE_DELAY, data));
+        }
+        cast
+    }
+
+    /// Browses the moves played from this warehouse in the terminal.
+    pub fn replay(&self, log: &[Move]) -> io::Result<()> {
+        let mut warehouse = self.clone();
+        let mut played = 0;
+        viewer::play(log.len() + 1, 0, |frame| {
+            while played < frame {
+                warehouse.apply(&log[played]);
+                played += 1;
+            }
+            while played > frame {
+                played -= 1;
+                warehouse.undo(&log[played]);
+            }
+            let last = match frame {
+                0 => "start".to_string(),
+                _ => log[frame - 1].to_string(),
+            };
+            format!(
+                "move {frame}/{}: {last}  gps: {}\n{warehouse}",
+                log.len(),
+                warehouse.gps()
+            )
+        })
+    }
+}
+
+impl<const WIDE: bool> Display for WareHouse<WIDE> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (w, h) = self.size();
+        for j in 0..h {
+            if j > 0 {
+                f.write_char('\n')?;
+            }
+            for i in 0..w {
+                f.write_char(self.cell((i, j)))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn solve<const WIDE: bool>(input: &str) -> Option<usize> {
+    let mut warehouse = WareHouse::<WIDE>::read(input);
+    warehouse.compute();
+
+    Some(warehouse.gps())
+}
+
+/// The path of the cast of `part`: `part` is appended to the file name, before its extension.
+pub fn cast_path(path: &str, part: u8) -> PathBuf {
+    let path = Path::new(path);
+    let mut name = path.file_stem().unwrap_or_default().to_os_string();
+    name.push(format!("-{part}"));
+    if let Some(extension) = path.extension() {
+        name.push(".");
+        name.push(extension);
+    }
+    path.with_file_name(name)
+}
+
+fn inspect_moves<const WIDE: bool>(input: &str, part: u8) {
+    let initial = WareHouse::<WIDE>::read(input);
+    let log = initial.clone().compute();
+
+    let cast: String = param("cast", String::new());
+    if !cast.is_empty() {
+        let path = cast_path(&cast, part);
+        if let Err(e) = fs::write(&path, initial.to_cast(&log)) {
+            eprintln!("could not write the cast to {}: {e}", path.display());
+        }
+    }
+    if param("visualize", false) {
+        if let Err(e) = initial.replay(&log) {
+            eprintln!("could not draw to the terminal: {e}");
+        }
+    }
+}
+
+/// Exports or replays the moves of the robot, as selected by the parameters.
+pub fn inspect(input: &str, part: u8) {
+    match part {
+        1 => inspect_moves::<false>(input, part),
+        _ => inspect_moves::<true>(input, part),
+    }
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    solve::<false>(input)
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    solve::<true>(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&crate::template::read_file_part("examples", DAY, 1));
+        assert_eq!(result, Some(2028));
+
+        let result = part_one(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Some(10092));
+    }
+
+    #[test]
+    fn records_moves() {
+        let input = crate::template::read_file_part("examples", DAY, 1);
+        let mut warehouse = WareHouse::<false>::read(&input);
+        let log = warehouse.compute();
+
+        assert_eq!(log.len(), 15);
+        assert_eq!(log[0].to_string(), "< from (2,2), blocked");
+        assert_eq!(log[1].to_string(), "^ from (2,2)");
+        assert_eq!(log[4].to_string(), "> from (3,1), pushed 2 boxes");
+        assert_eq!(log[4].pushed, vec![(4, 1), (5, 1)]);
+        assert_eq!(log.iter().filter(|m| m.blocked).count(), 5);
+    }
+
+    #[test]
+    fn replays_and_undoes_moves() {
+        let input = crate::template::read_file("examples", DAY);
+        let initial = WareHouse::<true>::read(&input);
+        let mut computed = initial.clone();
+        let log = computed.compute();
+
+        let mut replayed = initial.clone();
+        log.iter().for_each(|m| replayed.apply(m));
+        assert_eq!(replayed, computed);
+        assert_eq!(replayed.gps(), 9021);
+
+        log.iter().rev().for_each(|m| replayed.undo(m));
+        assert_eq!(replayed, initial);
+    }
+
+    #[test]
+    fn renders_warehouse() {
+        let input = crate::template::read_file_part("examples", DAY, 1);
+        let map = input.split("\n\n").next().unwrap();
+        assert_eq!(WareHouse::<false>::read(&input).to_string(), map);
+
+        let wide = WareHouse::<true>::read(&input).to_string();
+        assert_eq!(wide.lines().nth(1), Some("##....[]..[]..##"));
+    }
+
+    #[test]
+    fn suffixes_cast_path_with_part() {
+        assert_eq!(cast_path("moves.cast", 1), PathBuf::from("moves-1.cast"));
+        assert_eq!(cast_path("out/moves", 2), PathBuf::from("out/moves-2"));
+    }
+
+    #[test]
+    fn exports_cast() {
+        let input = crate::template::read_file_part("examples", DAY, 1);
+        let initial = WareHouse::<false>::read(&input);
+        let log = initial.clone().compute();
+
+        let cast = initial.to_cast(&log);
+        let lines: Vec<&str> = cast.lines().collect();
+        assert_eq!(lines[0], "{\"version\": 2, \"width\": 40, \"height\": 9}");
+        assert_eq!(lines.len(), 2 + log.len());
+        assert!(lines[1].starts_with("[0.000, \"o\", \"\\u001b[2J"));
+        assert!(lines[16].starts_with("[0.750, \"o\", "));
+        assert!(lines[16].ends_with("move 15/15: < from (4,4), blocked\"]"));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Some(9021));
+    }
+}
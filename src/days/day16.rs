@@ -0,0 +1,647 @@
+use self::Direction::*;
+use crate::template::{param, Day, ParamKind, Parameter};
+use crossterm::style::{Color, Stylize};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// The day solved by this module.
+pub const DAY: Day = crate::day!(16);
+
+pub const PARAMETERS: &[Parameter] = &[
+    Parameter {
+        name: "optimal",
+        default: "false",
+        kind: ParamKind::Flag,
+        description: "draw every optimal route over the maze in part 1 to stderr",
+    },
+    Parameter {
+        name: "best",
+        default: "0",
+        kind: ParamKind::Count,
+        description:
+            "draw the given number of best distinct routes over the maze in part 1 to stderr",
+    },
+];
+
+/// Optimal routes drawn at most, their number grows exponentially with the ties.
+const MAX_OPTIMAL_ROUTES: usize = 1000;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Direction {
+    W,
+    E,
+    N,
+    S,
+}
+impl Direction {
+    pub fn left(&self) -> Direction {
+        match self {
+            W => S,
+            E => N,
+            N => W,
+            S => E,
+        }
+    }
+    pub fn right(&self) -> Direction {
+        match self {
+            W => N,
+            E => S,
+            N => E,
+            S => W,
+        }
+    }
+    fn next_post(&self, pos: (usize, usize)) -> (usize, usize) {
+        let (x, y) = pos;
+        match self {
+            W => (x - 1, y),
+            E => (x + 1, y),
+            N => (x, y - 1),
+            S => (x, y + 1),
+        }
+    }
+}
+
+/// A state of the search: the reindeer at a position facing a direction.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Path {
+    pub pos: (usize, usize),
+    pub dir: Direction,
+    pub end: (usize, usize),
+}
+impl Path {
+    pub fn read(input: &str) -> Path {
+        let mut start: (usize, usize) = (0, 0);
+        let mut end: (usize, usize) = (0, 0);
+
+        for (j, l) in input.lines().enumerate() {
+            for (i, c) in l.chars().enumerate() {
+                match c {
+                    'S' => {
+                        start = (i, j);
+                    }
+                    'E' => {
+                        end = (i, j);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Path {
+            pos: start,
+            end,
+            dir: E,
+        }
+    }
+
+    fn next_move(&self, score: usize, maze: &FxHashSet<(usize, usize)>) -> Option<(Path, usize)> {
+        if self.end == self.pos {
+            return None;
+        }
+
+        let pos = self.dir.next_post(self.pos);
+
+        if self.end != pos && !maze.contains(&pos) {
+            return None;
+        }
+        Some((Path { pos, ..*self }, score + 1))
+    }
+
+    fn next_moves(
+        &self,
+        score: usize,
+        maze: &FxHashSet<(usize, usize)>,
+    ) -> [Option<(Path, usize)>; 3] {
+        if self.end == self.pos {
+            return [const { None }; 3];
+        }
+        [
+            self.next_move(score, maze),
+            Path {
+                dir: self.dir.left(),
+                ..*self
+            }
+            .next_move(score + 1000, maze),
+            Path {
+                dir: self.dir.right(),
+                ..*self
+            }
+            .next_move(score + 1000, maze),
+        ]
+    }
+}
+
+/// The open tiles of the maze, the start and end tiles excluded.
+pub fn read_maze(input: &str) -> FxHashSet<(usize, usize)> {
+    let mut maze = FxHashSet::default();
+    for (j, l) in input.lines().enumerate() {
+        for (i, c) in l.chars().enumerate() {
+            if c == '.' {
+                maze.insert((i, j));
+            }
+        }
+    }
+    maze
+}
+
+/// A way from the start to the end of the maze.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Route {
+    pub score: usize,
+    /// The states of the reindeer, from the start to the end.
+    pub steps: Vec<Path>,
+}
+
+impl Route {
+    fn new(steps: Vec<Path>) -> Self {
+        let score = steps
+            .windows(2)
+            .map(|w| if w[0].dir == w[1].dir { 1 } else { 1001 })
+            .sum();
+        Self { score, steps }
+    }
+
+    pub fn turns(&self) -> usize {
+        self.steps
+            .windows(2)
+            .filter(|w| w[0].dir != w[1].dir)
+            .count()
+    }
+}
+
+/// Tiles and moves a search must avoid.
+#[derive(Default)]
+struct Detours {
+    tiles: FxHashSet<(usize, usize)>,
+    moves: FxHashSet<(Path, Path)>,
+}
+
+/// Dijkstra from `start`, returns the best score of every reachable state along with the states
+/// preceding it on its best ways.
+fn search(
+    maze: &FxHashSet<(usize, usize)>,
+    start: Path,
+    detours: &Detours,
+) -> (FxHashMap<Path, usize>, FxHashMap<Path, Vec<Path>>) {
+    let mut scores: FxHashMap<Path, usize> = FxHashMap::default();
+    let mut previous: FxHashMap<Path, Vec<Path>> = FxHashMap::default();
+    let mut queue = BinaryHeap::from([Reverse((0, start))]);
+    scores.insert(start, 0);
+
+    while let Some(Reverse((score, p))) = queue.pop() {
+        if scores[&p] < score {
+            continue;
+        }
+        for (next, next_score) in p.next_moves(score, maze).into_iter().flatten() {
+            if detours.tiles.contains(&next.pos) || detours.moves.contains(&(p, next)) {
+                continue;
+            }
+            match scores.get(&next) {
+                Some(best) if *best < next_score => {}
+                Some(best) if *best == next_score => previous.entry(next).or_default().push(p),
+                _ => {
+                    scores.insert(next, next_score);
+                    previous.insert(next, vec![p]);
+                    queue.push(Reverse((next_score, next)));
+                }
+            }
+        }
+    }
+    (scores, previous)
+}
+
+/// The end states of best score, and that score.
+fn best_ends(scores: &FxHashMap<Path, usize>) -> (Vec<Path>, Option<usize>) {
+    let best = scores
+        .iter()
+        .filter(|(p, _)| p.pos == p.end)
+        .map(|(_, s)| *s)
+        .min();
+    let ends = scores
+        .iter()
+        .filter(|(p, s)| p.pos == p.end && Some(**s) == best)
+        .map(|(p, _)| *p)
+        .collect();
+    (ends, best)
+}
+
+/// Every route of best score, at most `limit` of them.
+pub fn optimal_routes(input: &str, limit: usize) -> Vec<Route> {
+    let maze = read_maze(input);
+    let start = Path::read(input);
+    let (scores, previous) = search(&maze, start, &Detours::default());
+    let (ends, _) = best_ends(&scores);
+
+    // walk the best predecessors back from the end, depth first.
+    let mut routes = vec![];
+    let mut stack: Vec<Vec<Path>> = ends.into_iter().map(|e| vec![e]).collect();
+    while let Some(reversed) = stack.pop() {
+        if routes.len() == limit {
+            break;
+        }
+        let last = *reversed.last().unwrap();
+        if last == start {
+            routes.push(Route::new(reversed.into_iter().rev().collect()));
+            continue;
+        }
+        for p in previous.get(&last).into_iter().flatten() {
+            let mut longer = reversed.clone();
+            longer.push(*p);
+            stack.push(longer);
+        }
+    }
+    routes.sort_by_key(|r| r.steps.clone());
+    routes
+}
+
+/// The best route from `start`, if any.
+fn best_route(maze: &FxHashSet<(usize, usize)>, start: Path, detours: &Detours) -> Option<Route> {
+    let (scores, previous) = search(maze, start, detours);
+    let (ends, _) = best_ends(&scores);
+
+    let mut steps = vec![*ends.iter().min()?];
+    while let Some(p) = previous.get(steps.last()?).and_then(|p| p.iter().min()) {
+        steps.push(*p);
+    }
+    steps.reverse();
+    Some(Route::new(steps))
+}
+
+/// The `k` best routes never going twice through a tile, best first, with Yen's algorithm:
+/// each new route leaves one of the previous ones at some step, and avoids both the tiles before
+/// that step and the moves already taken from there by previous routes sharing the same beginning.
+pub fn best_routes(input: &str, k: usize) -> Vec<Route> {
+    let maze = read_maze(input);
+    let start = Path::read(input);
+
+    let Some(first) = best_route(&maze, start, &Detours::default()) else {
+        return vec![];
+    };
+    let mut routes = vec![first];
+    let mut candidates: Vec<Route> = vec![];
+
+    while routes.len() < k {
+        let last = routes.last().unwrap();
+        for i in 0..last.steps.len() - 1 {
+            let spur = last.steps[i];
+            let root = &last.steps[..=i];
+            // routes can only part where the reindeer has a choice.
+            if spur.next_moves(0, &maze).iter().flatten().count() < 2 {
+                continue;
+            }
+
+            let detours = Detours {
+                tiles: root[..i].iter().map(|p| p.pos).collect(),
+                moves: routes
+                    .iter()
+                    .filter(|r| r.steps.len() > i + 1 && r.steps[..=i] == *root)
+                    .map(|r| (r.steps[i], r.steps[i + 1]))
+                    .collect(),
+            };
+            if let Some(spur_route) = best_route(&maze, spur, &detours) {
+                let steps = [&root[..i], &spur_route.steps].concat();
+                let candidate = Route::new(steps);
+                if !candidates.contains(&candidate) && !routes.contains(&candidate) {
+                    candidates.push(candidate);
+                }
+            }
+        }
+
+        let Some((best, _)) = candidates
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, r)| (r.score, r.steps.clone()))
+        else {
+            break;
+        };
+        routes.push(candidates.swap_remove(best));
+    }
+    routes
+}
+
+/// Glyph of the tile where the reindeer turns from `from` to `to`.
+fn corner(from: Direction, to: Direction) -> char {
+    match (from, to) {
+        (E, N) | (S, W) => '┘',
+        (E, S) | (N, W) => '┐',
+        (W, N) | (S, E) => '└',
+        (W, S) | (N, E) => '┌',
+        _ => '+',
+    }
+}
+
+/// Draws the routes over the maze: arrows along the way, corners where the reindeer turns.
+/// Tiles shared by several routes show the first one. Colours tell routes apart when `color` is set.
+pub fn render(input: &str, routes: &[Route], color: bool) -> String {
+    const COLORS: [Color; 6] = [
+        Color::Green,
+        Color::Cyan,
+        Color::Yellow,
+        Color::Magenta,
+        Color::Blue,
+        Color::Red,
+    ];
+
+    let mut overlay: FxHashMap<(usize, usize), (char, usize)> = FxHashMap::default();
+    for (r, route) in routes.iter().enumerate() {
+        for w in route.steps.windows(2) {
+            let glyph = if w[0].dir == w[1].dir {
+                match w[0].dir {
+                    W => '<',
+                    E => '>',
+                    N => '^',
+                    S => 'v',
+                }
+            } else {
+                corner(w[0].dir, w[1].dir)
+            };
+            overlay.entry(w[0].pos).or_insert((glyph, r));
+        }
+    }
+
+    let mut text: String = input
+        .lines()
+        .enumerate()
+        .map(|(j, l)| {
+            let line: String = l
+                .chars()
+                .enumerate()
+                .map(|(i, c)| match overlay.get(&(i, j)) {
+                    Some((glyph, r)) if c != 'S' && color => {
+                        glyph.with(COLORS[r % COLORS.len()]).to_string()
+                    }
+                    Some((glyph, _)) if c != 'S' => glyph.to_string(),
+                    _ => c.to_string(),
+                })
+                .collect();
+            line + "\n"
+        })
+        .collect();
+
+    for (r, route) in routes.iter().enumerate() {
+        let legend = format!(
+            "route {}: score {}, {} steps, {} turns",
+            r + 1,
+            route.score,
+            route.steps.len() - 1,
+            route.turns()
+        );
+        let legend = if color {
+            legend.with(COLORS[r % COLORS.len()]).to_string()
+        } else {
+            legend
+        };
+        text.push_str(&legend);
+        text.push('\n');
+    }
+    text
+}
+
+/// Draws the optimal or best routes of part 1 to stderr, as selected by the parameters.
+pub fn inspect(input: &str, part: u8) {
+    if part != 1 {
+        return;
+    }
+    if param("optimal", false) {
+        eprint!(
+            "{}",
+            render(input, &optimal_routes(input, MAX_OPTIMAL_ROUTES), true)
+        );
+    }
+    let best = param("best", 0);
+    if best > 0 {
+        eprint!("{}", render(input, &best_routes(input, best), true));
+    }
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let maze = &read_maze(input);
+
+    // ~ Dijkstra
+    let mut best_moves: FxHashMap<Path, usize> = Default::default();
+    let mut visited: FxHashSet<Path> = Default::default();
+    best_moves.insert(Path::read(input), 0);
+    let mut next_moves = best_moves.clone();
+
+    while let Some((p, score)) = next_moves
+        .iter()
+        // .filter(|(p, _)| !visited.contains(p))
+        .min_by(|(_, s1), (_, s2)| s1.cmp(s2))
+    {
+        let p = *p;
+        let score = *score;
+        visited.insert(p);
+        next_moves.remove(&p);
+        for (p, score) in p.next_moves(score, maze).iter().flatten() {
+            if !visited.contains(p) {
+                next_moves
+                    .entry(*p)
+                    .and_modify(|best| {
+                        if *score < *best {
+                            *best = *score;
+                        }
+                    })
+                    .or_insert(*score);
+            }
+            best_moves
+                .entry(*p)
+                .and_modify(|best| {
+                    if *score < *best {
+                        *best = *score;
+                    }
+                })
+                .or_insert(*score);
+        }
+    }
+
+    best_moves
+        .iter()
+        .filter(|(p, _)| p.pos == p.end)
+        .min_by(|(_, s1), (_, s2)| s1.cmp(s2))
+        .map(|(_, score)| *score)
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let maze = &read_maze(input);
+
+    // ~ Dijkstra
+    let mut best_moves: FxHashMap<Path, (usize, FxHashSet<(usize, usize)>)> = Default::default();
+    let mut visited: FxHashSet<Path> = Default::default();
+    let init = Path::read(input);
+
+    best_moves.insert(init, (0, FxHashSet::from_iter([init.pos])));
+    let mut next_moves: FxHashMap<Path, usize> = Default::default();
+    next_moves.insert(init, 0);
+
+    while let Some((p, score)) = next_moves
+        .iter()
+        // .filter(|(p, _)| !visited.contains(*p))
+        .min_by(|(_, s1), (_, s2)| s1.cmp(s2))
+    {
+        let p = *p;
+        let score = *score;
+        let on_path = best_moves.get(&p).cloned().unwrap().1;
+
+        visited.insert(p);
+        next_moves.remove(&p);
+        for (p, score) in p.next_moves(score, maze).iter().flatten() {
+            if !visited.contains(p) {
+                next_moves
+                    .entry(*p)
+                    .and_modify(|best| {
+                        if *score < *best {
+                            *best = *score;
+                        }
+                    })
+                    .or_insert(*score);
+            }
+            best_moves
+                .entry(*p)
+                .and_modify(|(best, on_path_previous)| {
+                    if *score <= *best {
+                        if *score < *best {
+                            on_path_previous.drain();
+                        }
+                        *best = *score;
+                        for op in on_path.iter() {
+                            on_path_previous.insert(*op);
+                        }
+                        on_path_previous.insert(p.pos);
+                    }
+                })
+                .or_insert({
+                    let mut on_path = on_path.clone();
+                    on_path.insert(p.pos);
+                    (*score, on_path)
+                });
+        }
+    }
+
+    best_moves
+        .iter()
+        .filter(|(p, _)| p.pos == p.end)
+        .min_by(|(_, (s1, _)), (_, (s2, _))| s1.cmp(s2))
+        .map(|(_, (_, on_p))| on_p.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Some(7036));
+        let result = part_one(&crate::template::read_file_part("examples", DAY, 1));
+        assert_eq!(result, Some(11048));
+    }
+
+    #[test]
+    fn lists_optimal_routes() {
+        let input = crate::template::read_file("examples", DAY);
+        let routes = optimal_routes(&input, 100);
+        assert_eq!(routes.len(), 3);
+        assert!(routes.iter().all(|r| r.score == 7036 && r.turns() == 7));
+
+        let tiles: FxHashSet<_> = routes
+            .iter()
+            .flat_map(|r| &r.steps)
+            .map(|p| p.pos)
+            .collect();
+        assert_eq!(Some(tiles.len()), part_two(&input));
+
+        assert_eq!(optimal_routes(&input, 2).len(), 2);
+    }
+
+    #[test]
+    fn lists_best_routes() {
+        let input = crate::template::read_file("examples", DAY);
+        let routes = best_routes(&input, 5);
+        let scores: Vec<usize> = routes.iter().map(|r| r.score).collect();
+        assert_eq!(scores, vec![7036, 7036, 7036, 9040, 10028]);
+
+        for route in &routes {
+            let tiles: FxHashSet<_> = route.steps.iter().map(|p| p.pos).collect();
+            assert_eq!(
+                tiles.len(),
+                route.steps.len(),
+                "routes never go through a tile twice"
+            );
+        }
+        let optimal = optimal_routes(&input, 100);
+        assert!(optimal.iter().all(|r| routes[..3].contains(r)));
+    }
+
+    /// Scores of every route never going twice through a tile, by depth-first search.
+    fn brute_force_scores(input: &str, max_score: usize) -> Vec<usize> {
+        fn walk(
+            maze: &FxHashSet<(usize, usize)>,
+            p: Path,
+            score: usize,
+            max_score: usize,
+            seen: &mut FxHashSet<(usize, usize)>,
+            scores: &mut Vec<usize>,
+        ) {
+            if p.pos == p.end {
+                scores.push(score);
+                return;
+            }
+            for (next, next_score) in p.next_moves(score, maze).into_iter().flatten() {
+                if next_score <= max_score && seen.insert(next.pos) {
+                    walk(maze, next, next_score, max_score, seen, scores);
+                    seen.remove(&next.pos);
+                }
+            }
+        }
+
+        let start = Path::read(input);
+        let mut scores = vec![];
+        let mut seen = FxHashSet::from_iter([start.pos]);
+        walk(
+            &read_maze(input),
+            start,
+            0,
+            max_score,
+            &mut seen,
+            &mut scores,
+        );
+        scores.sort_unstable();
+        scores
+    }
+
+    #[test]
+    fn best_routes_match_brute_force() {
+        for input in [
+            crate::template::read_file("examples", DAY),
+            crate::template::read_file_part("examples", DAY, 1),
+        ] {
+            let expected = brute_force_scores(&input, 14_000);
+            let scores: Vec<usize> = best_routes(&input, expected.len())
+                .iter()
+                .map(|r| r.score)
+                .collect();
+            assert_eq!(scores, expected);
+        }
+    }
+
+    #[test]
+    fn renders_routes() {
+        let input = crate::template::read_file_part("examples", DAY, 1);
+        let routes = best_routes(&input, 1);
+        let text = render(&input, &routes, false);
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[2], "#.#.#.#.#.#.#.#^#");
+        assert_eq!(lines[7], "#^#v..#.#.#┌>>>┘#");
+        assert_eq!(lines[15], "#S#└>┘..........#");
+        assert_eq!(lines[17], "route 1: score 11048, 48 steps, 11 turns");
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Some(45));
+        let result = part_two(&crate::template::read_file_part("examples", DAY, 1));
+        assert_eq!(result, Some(64));
+    }
+}
@@ -0,0 +1,1107 @@
+use crate::template::{param, Day, ParamKind, Parameter};
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use tinyjson::JsonValue;
+
+/// The day solved by this module.
+pub const DAY: Day = crate::day!(17);
+
+pub const PARAMETERS: &[Parameter] = &[
+    Parameter {
+        name: "trace",
+        default: "false",
+        kind: ParamKind::Flag,
+        description: "print the disassembled program and its execution trace for part 1 to stderr",
+    },
+    Parameter {
+        name: "trace_json",
+        default: "",
+        kind: ParamKind::Text,
+        description: "path to export the execution trace of part 1 as JSON",
+    },
+];
+
+pub type Val = isize;
+
+/// The 3-bit computer: registers, instruction pointer, program and output.
+#[derive(Debug, Default, Clone)]
+pub struct Computer {
+    pub a: Val,
+    pub b: Val,
+    pub c: Val,
+    pub sp: usize,
+    pub instrs: Vec<u8>,
+    pub out: Vec<u8>,
+}
+
+impl Computer {
+    pub fn read(input: &str) -> Computer {
+        let mut lines = input.lines();
+
+        let a = lines.next().unwrap()["Register A:".len()..]
+            .trim()
+            .parse()
+            .unwrap();
+        let b = lines.next().unwrap()["Register A:".len()..]
+            .trim()
+            .parse()
+            .unwrap();
+        let c = lines.next().unwrap()["Register A:".len()..]
+            .trim()
+            .parse()
+            .unwrap();
+        lines.next();
+        let instrs: Vec<u8> = lines.next().unwrap()["Program: ".len()..]
+            .trim()
+            .split(',')
+            .map(|i| i.parse().unwrap())
+            .collect();
+
+        assert!(instrs.iter().all(|i| *i < 8));
+
+        Computer {
+            a,
+            b,
+            c,
+            instrs,
+            sp: 0,
+            out: vec![],
+        }
+    }
+    fn get_operand(&mut self) -> Option<u8> {
+        if self.sp >= self.instrs.len() {
+            eprintln!("end of instrs reach !");
+            return None;
+        }
+        self.sp += 1;
+
+        Some(self.instrs[self.sp - 1])
+    }
+    fn combo(&self, v: u8) -> Val {
+        match v {
+            v if v <= 3 => v as Val,
+            4 => self.a,
+            5 => self.b,
+            6 => self.c,
+            7 => panic!("7 combo found !!"),
+            _ => unreachable!("no operand > 7"),
+        }
+    }
+    fn adv(&mut self) {
+        if let Some(operand) = self.get_operand() {
+            self.a >>= self.combo(operand); //TODO : check truncated
+        }
+    }
+    fn bxl(&mut self) {
+        if let Some(operand) = self.get_operand() {
+            self.b ^= operand as Val;
+        }
+    }
+    fn bst(&mut self) {
+        if let Some(operand) = self.get_operand() {
+            self.b = self.combo(operand) % 8;
+        }
+    }
+    fn jnz(&mut self) {
+        if let Some(operand) = self.get_operand() {
+            if self.a != 0 {
+                self.sp = operand as usize;
+            }
+        }
+    }
+    fn bxc(&mut self) {
+        if self.get_operand().is_some() {
+            self.b ^= self.c;
+        }
+    }
+    fn out(&mut self) {
+        if let Some(operand) = self.get_operand() {
+            self.out.push((self.combo(operand) % 8) as u8);
+        }
+    }
+    fn bdv(&mut self) {
+        if let Some(operand) = self.get_operand() {
+            self.b = self.a >> self.combo(operand);
+        }
+    }
+    fn cdv(&mut self) {
+        if let Some(operand) = self.get_operand() {
+            self.c = self.a >> self.combo(operand);
+        }
+    }
+    /// Executes one instruction, returns `false` once the program halted.
+    pub fn tick(&mut self) -> bool {
+        if self.sp >= self.instrs.len() {
+            return false;
+        }
+        let opcode = self.instrs[self.sp];
+        self.sp += 1;
+        match opcode {
+            0 => {
+                self.adv();
+            }
+            1 => {
+                self.bxl();
+            }
+            2 => {
+                self.bst();
+            }
+            3 => {
+                self.jnz();
+            }
+            4 => {
+                self.bxc();
+            }
+            5 => {
+                self.out();
+            }
+            6 => {
+                self.bdv();
+            }
+            7 => {
+                self.cdv();
+            }
+            _ => {
+                unreachable!("no >7 opcode")
+            }
+        }
+        self.sp < self.instrs.len()
+    }
+    /// Runs the program to completion, returns its comma-separated output.
+    pub fn process(&mut self) -> String {
+        while self.tick() {}
+        self.out.iter().join(",").trim().to_string()
+    }
+
+    /// Renders the program as a mnemonic listing, one instruction per line prefixed by its address.
+    pub fn listing(&self) -> String {
+        disassemble(&self.instrs)
+            .iter()
+            .enumerate()
+            .map(|(i, instr)| format!("{:>3}: {instr}", 2 * i))
+            .join("\n")
+    }
+
+    /// Runs the program to completion, recording the state of the computer after every tick.
+    pub fn trace(&mut self) -> Trace {
+        let mut steps = vec![];
+
+        while self.sp < self.instrs.len() {
+            let sp = self.sp;
+            let instruction = Instruction::decode(&self.instrs[sp..]);
+            self.tick();
+            steps.push(TraceStep {
+                sp,
+                instruction,
+                a: self.a,
+                b: self.b,
+                c: self.c,
+                next_sp: self.sp,
+                out: self.out.clone(),
+            });
+        }
+
+        Trace { steps }
+    }
+}
+
+/// Operand of the instructions reading a combo operand.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Combo {
+    Literal(u8),
+    A,
+    B,
+    C,
+    Reserved,
+}
+
+impl From<u8> for Combo {
+    fn from(v: u8) -> Self {
+        match v {
+            0..=3 => Combo::Literal(v),
+            4 => Combo::A,
+            5 => Combo::B,
+            6 => Combo::C,
+            _ => Combo::Reserved,
+        }
+    }
+}
+
+impl Display for Combo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Combo::Literal(v) => write!(f, "{v}"),
+            Combo::A => f.write_str("A"),
+            Combo::B => f.write_str("B"),
+            Combo::C => f.write_str("C"),
+            Combo::Reserved => f.write_str("<reserved 7>"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    Adv(Combo),
+    Bxl(u8),
+    Bst(Combo),
+    Jnz(u8),
+    Bxc,
+    Out(Combo),
+    Bdv(Combo),
+    Cdv(Combo),
+    /// An opcode without operand, at the end of the program.
+    Truncated(u8),
+}
+
+impl Instruction {
+    /// Decodes the instruction at the start of `instrs`.
+    fn decode(instrs: &[u8]) -> Instruction {
+        let Some(operand) = instrs.get(1).copied() else {
+            return Instruction::Truncated(instrs[0]);
+        };
+        match instrs[0] {
+            0 => Instruction::Adv(operand.into()),
+            1 => Instruction::Bxl(operand),
+            2 => Instruction::Bst(operand.into()),
+            3 => Instruction::Jnz(operand),
+            4 => Instruction::Bxc,
+            5 => Instruction::Out(operand.into()),
+            6 => Instruction::Bdv(operand.into()),
+            7 => Instruction::Cdv(operand.into()),
+            _ => unreachable!("no >7 opcode"),
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Adv(op) => write!(f, "adv {op}"),
+            Instruction::Bxl(op) => write!(f, "bxl {op}"),
+            Instruction::Bst(op) => write!(f, "bst {op}"),
+            Instruction::Jnz(op) => write!(f, "jnz {op}"),
+            Instruction::Bxc => f.write_str("bxc"),
+            Instruction::Out(op) => write!(f, "out {op}"),
+            Instruction::Bdv(op) => write!(f, "bdv {op}"),
+            Instruction::Cdv(op) => write!(f, "cdv {op}"),
+            Instruction::Truncated(opcode) => write!(f, "<opcode {opcode} without operand>"),
+        }
+    }
+}
+
+/// Decodes a program, each instruction being an opcode followed by its operand.
+pub fn disassemble(instrs: &[u8]) -> Vec<Instruction> {
+    (0..instrs.len())
+        .step_by(2)
+        .map(|sp| Instruction::decode(&instrs[sp..]))
+        .collect()
+}
+
+/// State of the computer after executing `instruction` at `sp`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceStep {
+    pub sp: usize,
+    pub instruction: Instruction,
+    pub a: Val,
+    pub b: Val,
+    pub c: Val,
+    pub next_sp: usize,
+    pub out: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Trace {
+    pub steps: Vec<TraceStep>,
+}
+
+impl Trace {
+    pub fn to_json(&self) -> String {
+        JsonValue::from(self).stringify().unwrap()
+    }
+}
+
+impl Display for Trace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let width = |reg: fn(&TraceStep) -> Val| {
+            self.steps
+                .iter()
+                .map(|s| reg(s).to_string().len())
+                .max()
+                .unwrap_or(0)
+                .max(1)
+        };
+        let (wa, wb, wc) = (width(|s| s.a), width(|s| s.b), width(|s| s.c));
+
+        writeln!(
+            f,
+            "{:>5} | {:>3} | {:<9} | {:>wa$} | {:>wb$} | {:>wc$} | out",
+            "step", "sp", "instr", "A", "B", "C"
+        )?;
+        for (i, s) in self.steps.iter().enumerate() {
+            writeln!(
+                f,
+                "{i:>5} | {:>3} | {:<9} | {:>wa$} | {:>wb$} | {:>wc$} | {}",
+                s.sp,
+                s.instruction.to_string(),
+                s.a,
+                s.b,
+                s.c,
+                s.out.iter().join(",")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&Trace> for JsonValue {
+    fn from(value: &Trace) -> Self {
+        let steps = value
+            .steps
+            .iter()
+            .map(|s| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert("sp".into(), JsonValue::Number(s.sp as f64));
+                map.insert(
+                    "instruction".into(),
+                    JsonValue::String(s.instruction.to_string()),
+                );
+                map.insert("a".into(), JsonValue::Number(s.a as f64));
+                map.insert("b".into(), JsonValue::Number(s.b as f64));
+                map.insert("c".into(), JsonValue::Number(s.c as f64));
+                map.insert("next_sp".into(), JsonValue::Number(s.next_sp as f64));
+                map.insert(
+                    "out".into(),
+                    JsonValue::Array(s.out.iter().map(|o| JsonValue::Number(*o as f64)).collect()),
+                );
+                JsonValue::Object(map)
+            })
+            .collect();
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("steps".into(), JsonValue::Array(steps));
+        JsonValue::Object(map)
+    }
+}
+
+pub fn part_one(input: &str) -> Option<String> {
+    let mut computer = Computer::read(input);
+
+    Some(computer.process())
+}
+
+/// Why no initial value of A can be found for a program to output itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuineError {
+    /// No value of A makes the program output itself.
+    NoSolution,
+    /// The generic search found no value of A making the program output itself.
+    NotFound,
+    /// The program does not have the shape the fast search relies on.
+    Unsupported(String),
+    /// The program runs an instruction with the reserved combo operand.
+    Invalid(String),
+}
+
+impl Display for QuineError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuineError::NoSolution => f.write_str("no value of A makes the program output itself"),
+            QuineError::NotFound => f.write_str(
+                "no value of A found making the program output itself, 3 bits of A per output",
+            ),
+            QuineError::Unsupported(reason) => write!(f, "unsupported program: {reason}"),
+            QuineError::Invalid(reason) => write!(f, "invalid program: {reason}"),
+        }
+    }
+}
+
+/// How a looping program consumes A.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoopShape {
+    /// Number of bits A is shifted right by on each turn of the loop.
+    pub shift: u32,
+    /// Number of values output on each turn of the loop.
+    pub outputs: usize,
+    /// For each output of a turn, the mask of the bits of A at the start of the turn it may
+    /// depend on.
+    pub influence: Vec<u64>,
+}
+
+/// What the value of a register may depend on, during the influence analysis.
+#[derive(Clone, Copy)]
+struct Symbolic {
+    /// For each bit of the register, the mask of the bits of A at the start of the turn it may
+    /// depend on.
+    deps: [u64; 64],
+    /// The bits of the register that may be set.
+    set: u64,
+    /// The value of the register, when it does not depend on A.
+    known: Option<u64>,
+}
+
+impl Symbolic {
+    const ZERO: Symbolic = Symbolic {
+        deps: [0; 64],
+        set: 0,
+        known: Some(0),
+    };
+
+    /// A at the start of the turn: each bit depends on itself.
+    fn a() -> Symbolic {
+        let mut a = Symbolic {
+            set: Val::MAX as u64,
+            known: None,
+            ..Symbolic::ZERO
+        };
+        for (i, deps) in a.deps.iter_mut().enumerate().take(Val::BITS as usize - 1) {
+            *deps = 1 << i;
+        }
+        a
+    }
+
+    fn literal(v: u8) -> Symbolic {
+        Symbolic {
+            set: u64::from(v),
+            known: Some(u64::from(v)),
+            ..Symbolic::ZERO
+        }
+    }
+
+    /// All the bits of A the value may depend on.
+    fn all_deps(&self) -> u64 {
+        self.deps.iter().fold(0, |acc, d| acc | d)
+    }
+
+    /// The value modulo 8.
+    fn low_bits(self) -> Symbolic {
+        let mut low = Symbolic {
+            set: self.set & 7,
+            known: self.known.map(|v| v & 7),
+            ..Symbolic::ZERO
+        };
+        low.deps[..3].copy_from_slice(&self.deps[..3]);
+        low
+    }
+
+    fn xor(mut self, other: Symbolic) -> Symbolic {
+        for (deps, other) in self.deps.iter_mut().zip(other.deps) {
+            *deps |= other;
+        }
+        self.set |= other.set;
+        self.known = self.known.zip(other.known).map(|(a, b)| a ^ b);
+        self
+    }
+
+    /// The value shifted right by any of the values `by` may take: each bit depends on all the
+    /// bits it may come from, and on the amount shifted by.
+    fn shr(self, by: Symbolic) -> Symbolic {
+        let mut shifted = Symbolic {
+            known: self
+                .known
+                .zip(by.known)
+                .map(|(v, s)| v.checked_shr(s as u32).unwrap_or(0)),
+            ..Symbolic::ZERO
+        };
+        let amounts = match by.known {
+            Some(s) => s.min(63)..=s.min(63),
+            None => 0..=by.set.min(63),
+        };
+        for s in amounts.map(|s| s as usize) {
+            for i in 0..64 - s {
+                shifted.deps[i] |= self.deps[i + s];
+            }
+            shifted.set |= self.set >> s;
+        }
+        let amount = by.all_deps();
+        for (i, deps) in shifted.deps.iter_mut().enumerate() {
+            if shifted.set & (1 << i) != 0 {
+                *deps |= amount;
+            }
+        }
+        shifted
+    }
+}
+
+/// Finds, for each output of a turn of the loop, the bits of A at the start of the turn it may
+/// depend on, by following the bits through the instructions of the turn.
+fn influence(program: &[Instruction]) -> Vec<u64> {
+    let mut registers = [Symbolic::a(), Symbolic::ZERO, Symbolic::ZERO];
+    let value = |registers: &[Symbolic; 3], op: &Combo| match op {
+        Combo::Literal(v) => Symbolic::literal(*v),
+        Combo::A => registers[0],
+        Combo::B => registers[1],
+        Combo::C => registers[2],
+        Combo::Reserved => unreachable!("rejected by `analyse`"),
+    };
+
+    let mut influence = vec![];
+    for instr in program {
+        match instr {
+            Instruction::Adv(op) => registers[0] = registers[0].shr(value(&registers, op)),
+            Instruction::Bxl(v) => registers[1] = registers[1].xor(Symbolic::literal(*v)),
+            Instruction::Bst(op) => registers[1] = value(&registers, op).low_bits(),
+            Instruction::Bxc => registers[1] = registers[1].xor(registers[2]),
+            Instruction::Out(op) => influence.push(value(&registers, op).low_bits().all_deps()),
+            Instruction::Bdv(op) => registers[1] = registers[0].shr(value(&registers, op)),
+            Instruction::Cdv(op) => registers[2] = registers[0].shr(value(&registers, op)),
+            Instruction::Jnz(_) | Instruction::Truncated(_) => {}
+        }
+    }
+    influence
+}
+
+/// Checks that a program is a single loop whose outputs on each turn only depend on the value of A
+/// at the start of the turn, so that a turn of the loop can be reversed by guessing the bits
+/// shifted out of A.
+pub fn analyse(instrs: &[u8]) -> Result<LoopShape, QuineError> {
+    let program = disassemble(instrs);
+
+    if let Some(instr @ Instruction::Truncated(_)) = program.last() {
+        return Err(QuineError::Unsupported(format!("ends with {instr}")));
+    }
+    if let Some(instr) = program.iter().find(|i| {
+        matches!(
+            i,
+            Instruction::Adv(Combo::Reserved)
+                | Instruction::Bst(Combo::Reserved)
+                | Instruction::Out(Combo::Reserved)
+                | Instruction::Bdv(Combo::Reserved)
+                | Instruction::Cdv(Combo::Reserved)
+        )
+    }) {
+        return Err(QuineError::Invalid(format!("`{instr}` is not valid")));
+    }
+
+    let jumps = program
+        .iter()
+        .filter(|i| matches!(i, Instruction::Jnz(_)))
+        .count();
+    let outputs = program
+        .iter()
+        .filter(|i| matches!(i, Instruction::Out(_)))
+        .count();
+
+    // without a loop, each `out` is executed once: the output is shorter than the program.
+    if jumps == 0 {
+        return Err(QuineError::NoSolution);
+    }
+    if jumps > 1 || program.last() != Some(&Instruction::Jnz(0)) {
+        return Err(QuineError::Unsupported(
+            "expecting a single loop, closed by `jnz 0` at the end".into(),
+        ));
+    }
+    if outputs == 0 || !instrs.len().is_multiple_of(outputs) {
+        return Err(QuineError::NoSolution);
+    }
+
+    let mut shift = 0;
+    for instr in &program {
+        match instr {
+            Instruction::Adv(Combo::Literal(v)) => shift += u32::from(*v),
+            Instruction::Adv(op) => {
+                return Err(QuineError::Unsupported(format!(
+                    "A is shifted by register {op}"
+                )))
+            }
+            _ => {}
+        }
+    }
+
+    // B and C must be written before being read, otherwise they carry state between turns.
+    let mut written = [false; 2];
+    for instr in &program {
+        let (reads, writes): (&[Combo], &[Combo]) = match instr {
+            Instruction::Bxl(_) => (&[Combo::B], &[Combo::B]),
+            Instruction::Bxc => (&[Combo::B, Combo::C], &[Combo::B]),
+            Instruction::Bst(op) => (&[*op], &[Combo::B]),
+            Instruction::Bdv(op) => (&[*op], &[Combo::B]),
+            Instruction::Cdv(op) => (&[*op], &[Combo::C]),
+            Instruction::Adv(op) | Instruction::Out(op) => (&[*op], &[]),
+            _ => (&[], &[]),
+        };
+        for (register, index) in [(Combo::B, 0), (Combo::C, 1)] {
+            if reads.contains(&register) && !written[index] {
+                return Err(QuineError::Unsupported(format!(
+                    "register {register} is read before being written in `{instr}`"
+                )));
+            }
+        }
+        for register in writes {
+            written[if *register == Combo::B { 0 } else { 1 }] = true;
+        }
+    }
+
+    Ok(LoopShape {
+        shift,
+        outputs,
+        influence: influence(&program),
+    })
+}
+
+/// Finds the smallest value of A for which the program outputs itself.
+///
+/// Programs with the shape checked by [`analyse`] are reversed turn by turn, the others are
+/// searched for by [`search_digits`].
+pub fn find_quine(computer: &Computer) -> Result<Val, QuineError> {
+    match analyse(&computer.instrs).and_then(|shape| reverse_loop(computer, shape)) {
+        Err(QuineError::Unsupported(_)) => search_digits(computer),
+        result => result,
+    }
+}
+
+/// Most instructions run from a value of A by [`search_digits`], as the program may not halt.
+const MAX_TICKS: usize = 100_000;
+
+/// The output of the program run from `a`, `None` if it does not halt within [`MAX_TICKS`]
+/// instructions or outputs more values than the program has.
+fn run_bounded(computer: &Computer, a: Val) -> Option<Vec<u8>> {
+    let mut cp = Computer {
+        a,
+        sp: 0,
+        out: vec![],
+        ..computer.clone()
+    };
+    for _ in 0..MAX_TICKS {
+        let running = cp.tick();
+        if cp.out.len() > cp.instrs.len() {
+            return None;
+        }
+        if !running {
+            return Some(cp.out);
+        }
+    }
+    None
+}
+
+/// Searches the smallest value of A for which any program outputs itself, assuming as in the
+/// puzzle that each output consumes 3 bits of A: the values of A outputting the last `n` values
+/// of the program are followed by 3 more bits, and those outputting the last `n + 1` are kept.
+fn search_digits(computer: &Computer) -> Result<Val, QuineError> {
+    let len = computer.instrs.len();
+    let mut candidates: Vec<Val> = vec![0];
+    for n in 1..=len {
+        let expected = &computer.instrs[len - n..];
+        candidates = candidates
+            .iter()
+            .filter(|a| **a >> (Val::BITS - 4) == 0)
+            .flat_map(|a| (0..8).map(move |low| (a << 3) | low))
+            .filter(|a| run_bounded(computer, *a).as_deref() == Some(expected))
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+    }
+    candidates.first().copied().ok_or(QuineError::NotFound)
+}
+
+/// Finds the smallest value of A for which a program of the given shape outputs itself.
+///
+/// The last turn of the loop runs with the most significant bits of A, each previous turn adds
+/// `shift` lower bits. Turns are reversed from the last one backwards: each one only guesses the
+/// bits its outputs depend on that are not known yet, the other bits are left to 0. Guesses are
+/// checked by running the turn, and the smallest complete guess is kept.
+fn reverse_loop(computer: &Computer, shape: LoopShape) -> Result<Val, QuineError> {
+    let LoopShape {
+        shift,
+        outputs,
+        influence,
+    } = shape;
+    let turns = computer.instrs.len() / outputs;
+
+    if shift == 0 {
+        // A never changes: the loop only ends if A is 0, after a single turn.
+        let mut cp = Computer {
+            a: 0,
+            ..computer.clone()
+        };
+        cp.process();
+        return if cp.out == cp.instrs {
+            Ok(0)
+        } else {
+            Err(QuineError::NoSolution)
+        };
+    }
+
+    let bits = shift * turns as u32;
+    if bits >= Val::BITS - 1 {
+        return Err(QuineError::Unsupported(format!("A would need {bits} bits")));
+    }
+
+    struct Search<'a> {
+        computer: &'a Computer,
+        shift: u32,
+        outputs: usize,
+        turns: usize,
+        /// Bits of A the outputs of any turn depend on, relative to the start of the turn.
+        influence: u64,
+        /// The loop must end after the last turn: A is below `1 << bits`.
+        bits: u32,
+        best: Option<Val>,
+    }
+
+    impl Search<'_> {
+        /// Runs the turn of the loop starting with `a`, up to the closing `jnz`.
+        fn run_turn(&self, a: Val) -> Vec<u8> {
+            let mut cp = Computer {
+                a,
+                sp: 0,
+                out: vec![],
+                ..self.computer.clone()
+            };
+            while cp.sp < cp.instrs.len() - 2 && cp.tick() {}
+            cp.out
+        }
+
+        /// Guesses the bits turn `turn` depends on, knowing the bits in `known` to be those of `a`.
+        fn guess(&mut self, turn: Option<usize>, a: Val, known: u64) {
+            let Some(turn) = turn else {
+                return self.complete(a, known);
+            };
+            let offset = self.shift * turn as u32;
+            let needed = (self.influence << offset) & ((1 << self.bits) - 1);
+            let unknown = needed & !known;
+            let expected = &self.computer.instrs[turn * self.outputs..][..self.outputs];
+            for guess in 0..1u64 << unknown.count_ones() {
+                // the unknown bits are 0 in `a`: it is the smallest value the guesses lead to.
+                let a = a | deposit(guess, unknown) as Val;
+                if self.best.is_some_and(|best| a >= best) {
+                    return;
+                }
+                if self.run_turn(a >> offset) == expected {
+                    self.guess(turn.checked_sub(1), a, known | unknown);
+                }
+            }
+        }
+
+        /// Completes a guess: A must not be 0 on the last turn, which no output may have required.
+        fn complete(&mut self, mut a: Val, known: u64) {
+            let last = self.shift * (self.turns as u32 - 1);
+            if a >> last == 0 {
+                let unknown = ((1 << self.bits) - (1 << last)) & !known;
+                if unknown == 0 {
+                    return;
+                }
+                a |= 1 << unknown.trailing_zeros();
+            }
+            let mut cp = Computer {
+                a,
+                sp: 0,
+                out: vec![],
+                ..self.computer.clone()
+            };
+            cp.process();
+            if cp.out == cp.instrs && self.best.is_none_or(|best| a < best) {
+                self.best = Some(a);
+            }
+        }
+    }
+
+    /// Spreads the bits of `value` over the set bits of `mask`, lowest first.
+    fn deposit(mut value: u64, mask: u64) -> u64 {
+        let mut deposited = 0;
+        let mut mask = mask;
+        while mask != 0 {
+            let bit = mask & mask.wrapping_neg();
+            if value & 1 != 0 {
+                deposited |= bit;
+            }
+            value >>= 1;
+            mask ^= bit;
+        }
+        deposited
+    }
+
+    let mut search = Search {
+        computer,
+        shift,
+        outputs,
+        turns,
+        influence: influence.iter().fold(0, |acc, i| acc | i),
+        bits,
+        best: None,
+    };
+    search.guess(Some(turns - 1), 0, 0);
+    search.best.ok_or(QuineError::NoSolution)
+}
+
+/// Prints or exports the trace of part 1, as selected by the parameters.
+pub fn inspect(input: &str, part: u8) {
+    if part != 1 {
+        return;
+    }
+    let computer = Computer::read(input);
+
+    if param("trace", false) {
+        eprintln!("{}\n\n{}", computer.listing(), computer.clone().trace());
+    }
+    let trace_json: String = param("trace_json", String::new());
+    if !trace_json.is_empty() {
+        let trace = computer.clone().trace();
+        if let Err(e) = fs::write(&trace_json, trace.to_json()) {
+            eprintln!("could not write the trace to {trace_json}: {e}");
+        }
+    }
+}
+
+pub fn part_two(input: &str) -> Result<Val, QuineError> {
+    let computer = Computer::read(input);
+
+    find_quine(&computer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic_instr() {
+        let mut computer = Computer {
+            c: 9,
+            instrs: vec![2, 6],
+            ..Default::default()
+        };
+        computer.process();
+        assert_eq!(computer.b, 1);
+
+        // let mut computer = Computer{a:10, instrs: vec![5,0,5,1,5,4],..Default::default()};
+        // assert_eq!("0,1,2",computer.process());
+
+        let mut computer = Computer {
+            a: 2024,
+            instrs: vec![0, 1, 5, 4, 3, 0],
+            ..Default::default()
+        };
+        assert_eq!("4,2,5,6,7,7,7,7,3,1,0", computer.process());
+        assert_eq!(computer.a, 0);
+    }
+
+    #[test]
+    fn disassembles_program() {
+        let computer = Computer::read(&crate::template::read_file("examples", DAY));
+        assert_eq!(computer.listing(), "  0: adv 1\n  2: out A\n  4: jnz 0");
+
+        assert_eq!(
+            disassemble(&[2, 4, 1, 7, 7, 5, 4, 3, 5, 5, 0, 3, 3, 0, 6]),
+            vec![
+                Instruction::Bst(Combo::A),
+                Instruction::Bxl(7),
+                Instruction::Cdv(Combo::B),
+                Instruction::Bxc,
+                Instruction::Out(Combo::B),
+                Instruction::Adv(Combo::Literal(3)),
+                Instruction::Jnz(0),
+                Instruction::Truncated(6),
+            ]
+        );
+    }
+
+    #[test]
+    fn traces_execution() {
+        let mut computer = Computer {
+            a: 10,
+            instrs: vec![5, 0, 5, 1, 5, 4],
+            ..Default::default()
+        };
+        let trace = computer.trace();
+        assert_eq!(trace.steps.len(), 3);
+        assert_eq!(trace.steps[2].instruction, Instruction::Out(Combo::A));
+        assert_eq!(trace.steps[2].out, vec![0, 1, 2]);
+        assert_eq!(trace.steps[2].next_sp, 6);
+        assert_eq!(computer.out, vec![0, 1, 2]);
+
+        let table = trace.to_string();
+        assert_eq!(table.lines().count(), 4);
+        assert!(table
+            .lines()
+            .nth(3)
+            .unwrap()
+            .ends_with("| out A     | 10 | 0 | 0 | 0,1,2"));
+
+        let json: JsonValue = trace.to_json().parse().unwrap();
+        let steps: &Vec<JsonValue> = json["steps"].get().unwrap();
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0]["instruction"], JsonValue::String("out 0".into()));
+        assert_eq!(steps[1]["a"], JsonValue::Number(10.0));
+    }
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Some("4,6,3,5,6,3,5,2,1,0".to_string()));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let mut computer = Computer {
+            a: 117440,
+            instrs: vec![0, 3, 5, 4, 3, 0],
+            ..Default::default()
+        };
+        assert_eq!("0,3,5,4,3,0".to_string(), computer.process());
+
+        let input = "Register A: 2024\nRegister B: 0\nRegister C: 0\n\nProgram: 0,3,5,4,3,0";
+        assert_eq!(part_two(input), Ok(117440));
+    }
+
+    fn quine(instrs: &[u8]) -> Result<Val, QuineError> {
+        find_quine(&Computer {
+            instrs: instrs.to_vec(),
+            ..Default::default()
+        })
+    }
+
+    fn outputs_itself(instrs: &[u8], a: Val) -> bool {
+        let mut computer = Computer {
+            a,
+            instrs: instrs.to_vec(),
+            ..Default::default()
+        };
+        computer.process();
+        computer.out == instrs
+    }
+
+    fn brute_force(instrs: &[u8], bits: u32) -> Option<Val> {
+        (0..1 << bits).find(|a| outputs_itself(instrs, *a))
+    }
+
+    #[test]
+    fn finds_quine_of_xor_mixing_program() {
+        // bst A, bxl 1, cdv B, bxl 5, bxc, adv 3, out B, jnz 0
+        let instrs = [2, 4, 1, 1, 7, 5, 1, 5, 4, 3, 0, 3, 5, 5, 3, 0];
+        assert_eq!(
+            analyse(&instrs),
+            Ok(LoopShape {
+                shift: 3,
+                outputs: 1,
+                // B is 3 bits of A, C is A shifted by up to 7 bits.
+                influence: vec![0x3ff],
+            })
+        );
+        let a = quine(&instrs).unwrap();
+        assert!(outputs_itself(&instrs, a));
+        assert!(!(a - 64..a).any(|a| outputs_itself(&instrs, a)));
+    }
+
+    #[test]
+    fn finds_quine_when_shifting_by_4_bits() {
+        // bst A, adv 2, adv 2, out B, jnz 0
+        let instrs = [2, 4, 0, 2, 0, 2, 5, 5, 3, 0];
+        assert_eq!(
+            analyse(&instrs),
+            Ok(LoopShape {
+                shift: 4,
+                outputs: 1,
+                influence: vec![0b111],
+            })
+        );
+        // each turn outputs the lowest 3 bits of a nibble, the last one needs its high bit for A != 0.
+        let expected = instrs
+            .iter()
+            .enumerate()
+            .map(|(i, d)| Val::from(*d) << (4 * i))
+            .sum::<Val>()
+            + (8 << 36);
+        assert_eq!(quine(&instrs), Ok(expected));
+    }
+
+    #[test]
+    fn finds_quine_with_two_outputs_per_turn() {
+        // bst A, out B, bdv 3, out B, adv 3, adv 3, jnz 0
+        let instrs = [2, 4, 5, 5, 6, 3, 5, 5, 0, 3, 0, 3, 3, 0];
+        assert_eq!(
+            analyse(&instrs),
+            Ok(LoopShape {
+                shift: 6,
+                outputs: 2,
+                influence: vec![0b111, 0b111_000],
+            })
+        );
+        let expected = instrs
+            .chunks(2)
+            .enumerate()
+            .map(|(i, d)| Val::from(d[0] + 8 * d[1]) << (6 * i))
+            .sum::<Val>();
+        assert_eq!(quine(&instrs), Ok(expected));
+    }
+
+    #[test]
+    fn agrees_with_brute_force_on_small_shifts() {
+        // the loop ends after the last turn: every quine is below `1 << bits`, the brute force
+        // finds the smallest one.
+        for (instrs, bits) in [
+            (vec![0, 1, 5, 4, 3, 0], 6),
+            (vec![0, 2, 5, 4, 3, 0], 12),
+            (vec![2, 4, 0, 1, 5, 5, 3, 0], 8),
+            (vec![0, 3, 5, 4, 3, 0], 18),
+            // bst A, cdv B, bxc, adv 1, out B, jnz 0
+            (vec![2, 4, 7, 5, 4, 0, 0, 1, 5, 5, 3, 0], 12),
+            // bdv 2, out B, adv 2, jnz 0: the lowest 2 bits of A are never output.
+            (vec![6, 2, 5, 5, 0, 2, 3, 0], 16),
+            // bst A, bdv B, out B, adv 1, jnz 0
+            (vec![2, 4, 6, 5, 5, 5, 0, 1, 3, 0], 10),
+        ] {
+            assert_eq!(
+                quine(&instrs).ok(),
+                brute_force(&instrs, bits),
+                "{instrs:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn analyses_bit_influence() {
+        // adv 3, out A: the output is the second group of 3 bits.
+        assert_eq!(
+            analyse(&[0, 3, 5, 4, 3, 0]).unwrap().influence,
+            vec![0b111_000]
+        );
+        // bst 0, bxl 7, out B: constant output.
+        assert_eq!(
+            analyse(&[2, 0, 1, 7, 5, 5, 0, 1, 3, 0]).unwrap().influence,
+            vec![0]
+        );
+        // bst 3, bxl 4, cdv B, out C, adv 1: B is known, C is A shifted by 7 bits.
+        assert_eq!(
+            analyse(&[2, 3, 1, 4, 7, 5, 5, 6, 0, 1, 3, 0])
+                .unwrap()
+                .influence,
+            vec![0b11_1000_0000]
+        );
+    }
+
+    #[test]
+    fn searches_programs_without_loop_shape() {
+        // A shifted by register B, set to 3 by the input.
+        let computer = Computer {
+            b: 3,
+            instrs: vec![0, 5, 5, 4, 3, 0],
+            ..Default::default()
+        };
+        assert!(matches!(
+            analyse(&computer.instrs),
+            Err(QuineError::Unsupported(_))
+        ));
+        let outputs_itself = |a| run_bounded(&computer, a).as_deref() == Some(&computer.instrs[..]);
+        let brute_force = (0..1 << 18).find(|a| outputs_itself(*a));
+        assert_eq!(find_quine(&computer).ok(), brute_force);
+        assert!(brute_force.is_some());
+    }
+
+    #[test]
+    fn reports_programs_without_solution_or_invalid() {
+        // no loop: a single output.
+        assert_eq!(quine(&[2, 4, 5, 5]), Err(QuineError::NoSolution));
+        // jnz at the start, looping forever unless A is 0.
+        assert_eq!(quine(&[3, 0, 5, 4, 3, 0]), Err(QuineError::NotFound));
+        // A shifted by B, 0: looping forever unless A is 0.
+        assert_eq!(quine(&[0, 5, 5, 4, 3, 0]), Err(QuineError::NotFound));
+        // B carries state between turns.
+        assert_eq!(quine(&[1, 3, 5, 5, 0, 3, 3, 0]), Err(QuineError::NotFound));
+        // reserved combo operand.
+        assert!(matches!(
+            quine(&[2, 7, 5, 4, 3, 0]),
+            Err(QuineError::Invalid(_))
+        ));
+    }
+}
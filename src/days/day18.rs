@@ -0,0 +1,281 @@
+use crate::template::{param, Day, ParamKind, Parameter};
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+
+/// The day solved by this module.
+pub const DAY: Day = crate::day!(18);
+
+pub const PARAMETERS: &[Parameter] = &[
+    Parameter {
+        name: "edge",
+        default: "70",
+        kind: ParamKind::Count,
+        description: "coordinate of the exit, the memory space spans 0..=edge on both axes",
+    },
+    Parameter {
+        name: "bytes",
+        default: "1024",
+        kind: ParamKind::Count,
+        description: "number of fallen bytes in part 1",
+    },
+];
+
+/// An error which can be returned when escaping the memory space.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemoryError {
+    /// A byte of the given line, starting at 1, falls outside of the memory space.
+    OutOfBounds {
+        line: usize,
+        byte: (usize, usize),
+        edge: usize,
+    },
+    /// The fallen bytes block the way to the exit.
+    Unreachable,
+    /// The exit can still be reached once every byte has fallen.
+    NeverBlocked,
+}
+
+impl Display for MemoryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MemoryError::OutOfBounds { line, byte, edge } => write!(
+                f,
+                "line {line}: byte {byte:?} is outside of the memory space 0..={edge}"
+            ),
+            MemoryError::Unreachable => f.write_str("the exit cannot be reached"),
+            MemoryError::NeverBlocked => {
+                f.write_str("the exit can still be reached once every byte has fallen")
+            }
+        }
+    }
+}
+
+/// The bytes falling in the memory space spanning `0..=edge` on both axes.
+pub fn read_blocks(input: &str, edge: usize) -> Result<Vec<(usize, usize)>, MemoryError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, l)| {
+            let byte = if let Some((x, y)) = l.split_once(',') {
+                (x.parse().unwrap(), y.parse().unwrap())
+            } else {
+                unreachable!("no coordinate to read");
+            };
+            if byte.0 > edge || byte.1 > edge {
+                return Err(MemoryError::OutOfBounds {
+                    line: i + 1,
+                    byte,
+                    edge,
+                });
+            }
+            Ok(byte)
+        })
+        .collect()
+}
+
+/// The cells of the memory space, `y * (edge + 1) + x`, along with their neighbours.
+fn neighbours(cell: usize, edge: usize) -> impl Iterator<Item = usize> {
+    let side = edge + 1;
+    let (x, y) = (cell % side, cell / side);
+    [
+        (x > 0).then(|| cell - 1),
+        (x < edge).then(|| cell + 1),
+        (y > 0).then(|| cell - side),
+        (y < edge).then(|| cell + side),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Number of steps from the top-left corner to the exit through cells not `blocked`.
+fn escape_memory(blocked: &[bool], edge: usize) -> Option<usize> {
+    let exit = blocked.len() - 1;
+    if blocked[0] {
+        return None;
+    }
+
+    let mut steps = vec![usize::MAX; blocked.len()];
+    steps[0] = 0;
+    let mut queue = VecDeque::from([0]);
+    while let Some(cell) = queue.pop_front() {
+        if cell == exit {
+            return Some(steps[cell]);
+        }
+        for next in neighbours(cell, edge) {
+            if !blocked[next] && steps[next] == usize::MAX {
+                steps[next] = steps[cell] + 1;
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Disjoint sets of cells, merged by size with path halving.
+struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+        }
+    }
+
+    fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+        if self.size[a] < self.size[b] {
+            (a, b) = (b, a);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+    }
+}
+
+/// The first byte after which the exit cannot be reached, `None` if it still can once every
+/// byte has fallen.
+///
+/// Bytes are lifted in reverse order from the fully corrupted space, joining the freed cells to
+/// their free neighbours: the byte blocking the way is the one whose lifting links both corners.
+pub fn first_blocking(input: &str, edge: usize) -> Result<Option<(usize, usize)>, MemoryError> {
+    let blocks = read_blocks(input, edge)?;
+    let side = edge + 1;
+    let exit = side * side - 1;
+
+    // a cell may be hit several times, it is free again once its first byte is lifted.
+    let mut falls = vec![0; side * side];
+    for (x, y) in &blocks {
+        falls[y * side + x] += 1;
+    }
+
+    let mut sets = DisjointSet::new(side * side);
+    for cell in 0..side * side {
+        for next in neighbours(cell, edge) {
+            if falls[cell] == 0 && falls[next] == 0 {
+                sets.union(cell, next);
+            }
+        }
+    }
+    if falls[0] == 0 && falls[exit] == 0 && sets.find(0) == sets.find(exit) {
+        return Ok(None);
+    }
+
+    for &(x, y) in blocks.iter().rev() {
+        let cell = y * side + x;
+        falls[cell] -= 1;
+        if falls[cell] > 0 {
+            continue;
+        }
+        for next in neighbours(cell, edge) {
+            if falls[next] == 0 {
+                sets.union(cell, next);
+            }
+        }
+        if falls[0] == 0 && falls[exit] == 0 && sets.find(0) == sets.find(exit) {
+            return Ok(Some((x, y)));
+        }
+    }
+
+    unreachable!("the exit is reachable from the empty memory space")
+}
+
+pub fn part_one(input: &str) -> Result<usize, MemoryError> {
+    let edge = param("edge", 70);
+    let mut blocked = vec![false; (edge + 1) * (edge + 1)];
+    for (x, y) in read_blocks(input, edge)?
+        .into_iter()
+        .take(param("bytes", 1024))
+    {
+        blocked[y * (edge + 1) + x] = true;
+    }
+    escape_memory(&blocked, edge).ok_or(MemoryError::Unreachable)
+}
+
+pub fn part_two(input: &str) -> Result<String, MemoryError> {
+    let byte = first_blocking(input, param("edge", 70))?.ok_or(MemoryError::NeverBlocked)?;
+    Ok(format!("{byte:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::with_params;
+
+    #[test]
+    fn test_part_one() {
+        let result = with_params(&[("edge", "6"), ("bytes", "12")], || {
+            part_one(&crate::template::read_file("examples", DAY))
+        });
+        assert_eq!(result, Ok(22));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = with_params(&[("edge", "6")], || {
+            part_two(&crate::template::read_file("examples", DAY))
+        });
+        assert_eq!(result, Ok("(6, 1)".to_string()));
+    }
+
+    #[test]
+    fn rejects_bytes_outside_of_memory() {
+        let input = "1,2\n7,0\n";
+        assert_eq!(
+            read_blocks(input, 6),
+            Err(MemoryError::OutOfBounds {
+                line: 2,
+                byte: (7, 0),
+                edge: 6
+            })
+        );
+        let result = with_params(&[("edge", "6")], || part_one("0,7"));
+        assert!(result.is_err());
+        assert!(first_blocking(input, 6).is_err());
+    }
+
+    #[test]
+    fn matches_byte_by_byte_search() {
+        let mut state = 0x1818_u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..50 {
+            let edge = 1 + next() as usize % 8;
+            let input: Vec<String> = (0..next() % 40)
+                .map(|_| {
+                    let (x, y) = (next() as usize % (edge + 1), next() as usize % (edge + 1));
+                    format!("{x},{y}")
+                })
+                .collect();
+            let input = input.join("\n");
+
+            let mut blocked = vec![false; (edge + 1) * (edge + 1)];
+            let expected = read_blocks(&input, edge)
+                .unwrap()
+                .into_iter()
+                .find(|(x, y)| {
+                    blocked[y * (edge + 1) + x] = true;
+                    escape_memory(&blocked, edge).is_none()
+                });
+            assert_eq!(first_blocking(&input, edge), Ok(expected), "{input}");
+        }
+    }
+}
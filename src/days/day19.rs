@@ -0,0 +1,248 @@
+use crate::template::Day;
+use std::fmt::{Display, Formatter};
+
+/// The day solved by this module.
+pub const DAY: Day = crate::day!(19);
+
+/// The colours of stripes, in the order of the children of the trie nodes.
+const COLORS: [char; 5] = ['w', 'u', 'b', 'r', 'g'];
+
+fn color(c: u8) -> Option<usize> {
+    COLORS.iter().position(|color| *color as u8 == c)
+}
+
+/// Error returned when a line of the input has stripes of an unknown colour.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedColors {
+    /// Line of the input, starting at 1.
+    pub line: usize,
+    /// The unknown colours, in order of appearance.
+    pub colors: Vec<char>,
+}
+
+impl Display for UnsupportedColors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let colors: Vec<String> = self.colors.iter().map(|c| format!("`{c}`")).collect();
+        write!(
+            f,
+            "line {}: unsupported colors {}, stripes are one of {COLORS:?}",
+            self.line,
+            colors.join(", ")
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    /// Index of the child node by colour, 0 for none as the root is no child.
+    children: [u32; 5],
+    /// Whether a towel ends on this node.
+    towel: bool,
+}
+
+/// The available towels, in a trie of their stripes. Collecting towels panics on a colour of no
+/// stripe, which the input validation reports beforehand.
+#[derive(Debug, Clone)]
+pub struct Towels {
+    nodes: Vec<Node>,
+}
+
+impl<'a> FromIterator<&'a str> for Towels {
+    fn from_iter<T: IntoIterator<Item = &'a str>>(towels: T) -> Self {
+        let mut nodes = vec![Node::default()];
+        for towel in towels {
+            let mut node = 0;
+            for c in towel.bytes() {
+                let c = color(c).unwrap_or_else(|| panic!("unsupported color in `{towel}`"));
+                if nodes[node].children[c] == 0 {
+                    nodes[node].children[c] = nodes.len() as u32;
+                    nodes.push(Node::default());
+                }
+                node = nodes[node].children[c] as usize;
+            }
+            nodes[node].towel = true;
+        }
+        Self { nodes }
+    }
+}
+
+impl Towels {
+    /// Lengths of the towels matching the stripes of `design` from its start.
+    fn prefixes<'d>(&'d self, design: &'d [u8]) -> impl Iterator<Item = usize> + 'd {
+        design
+            .iter()
+            .scan(0, |node, c| {
+                *node = self.nodes[*node].children[color(*c)?] as usize;
+                (*node != 0).then_some(*node)
+            })
+            .enumerate()
+            .filter(|(_, node)| self.nodes[*node].towel)
+            .map(|(i, _)| i + 1)
+    }
+
+    /// Number of arrangements of towels for each suffix of `design`, by start of the suffix.
+    fn suffix_counts(&self, design: &str) -> Vec<usize> {
+        let design = design.as_bytes();
+        let mut counts = vec![0; design.len() + 1];
+        counts[design.len()] = 1;
+        for i in (0..design.len()).rev() {
+            counts[i] = self.prefixes(&design[i..]).map(|len| counts[i + len]).sum();
+        }
+        counts
+    }
+
+    /// Number of arrangements of towels displaying `design`.
+    pub fn count(&self, design: &str) -> usize {
+        self.suffix_counts(design)[0]
+    }
+
+    /// At most `cap` arrangements of towels displaying `design`, shorter towels first.
+    pub fn arrangements<'d>(&self, design: &'d str, cap: usize) -> Vec<Vec<&'d str>> {
+        let counts = self.suffix_counts(design);
+        let mut arrangements = vec![];
+        let mut current = vec![];
+        self.arrange(design, 0, &counts, cap, &mut current, &mut arrangements);
+        arrangements
+    }
+
+    fn arrange<'d>(
+        &self,
+        design: &'d str,
+        start: usize,
+        counts: &[usize],
+        cap: usize,
+        current: &mut Vec<&'d str>,
+        arrangements: &mut Vec<Vec<&'d str>>,
+    ) {
+        if arrangements.len() >= cap {
+            return;
+        }
+        if start == design.len() {
+            arrangements.push(current.clone());
+            return;
+        }
+        for len in self.prefixes(&design.as_bytes()[start..]) {
+            // only follow towels leaving a suffix which can be displayed.
+            if counts[start + len] > 0 {
+                current.push(&design[start..start + len]);
+                self.arrange(design, start + len, counts, cap, current, arrangements);
+                current.pop();
+            }
+        }
+    }
+}
+
+fn read_input(input: &str) -> Result<(Towels, Vec<&str>), UnsupportedColors> {
+    for (line, l) in input.lines().enumerate() {
+        // only the towels are separated, designs are a single run of colors.
+        let separators: &[char] = if line == 0 { &[',', ' '] } else { &[] };
+        let mut colors = vec![];
+        // a CRLF line ending may leave a `\r` on the last line.
+        for c in l.trim_end().chars().filter(|c| !separators.contains(c)) {
+            if !COLORS.contains(&c) && !colors.contains(&c) {
+                colors.push(c);
+            }
+        }
+        if !colors.is_empty() {
+            return Err(UnsupportedColors {
+                line: line + 1,
+                colors,
+            });
+        }
+    }
+
+    let towels = input
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split(",")
+        .map(|w| w.trim())
+        .filter(|t| !t.is_empty())
+        .collect();
+    let patterns: Vec<_> = input
+        .lines()
+        .skip(2)
+        .map(str::trim)
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    Ok((towels, patterns))
+}
+
+pub fn part_one(input: &str) -> Result<usize, UnsupportedColors> {
+    let (towels, patterns) = read_input(input)?;
+
+    Ok(patterns.into_iter().filter(|p| towels.count(p) > 0).count())
+}
+
+pub fn part_two(input: &str) -> Result<usize, UnsupportedColors> {
+    let (towels, patterns) = read_input(input)?;
+
+    Ok(patterns.into_iter().map(|p| towels.count(p)).sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Ok(6));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Ok(16));
+    }
+
+    #[test]
+    fn lists_arrangements() {
+        let input = crate::template::read_file("examples", DAY);
+        let (towels, _) = read_input(&input).unwrap();
+        assert_eq!(
+            towels.arrangements("gbbr", 10),
+            [
+                vec!["g", "b", "b", "r"],
+                vec!["g", "b", "br"],
+                vec!["gb", "b", "r"],
+                vec!["gb", "br"]
+            ]
+        );
+        assert_eq!(towels.arrangements("rrbgbr", 3).len(), 3);
+        assert_eq!(towels.count("rrbgbr"), 6);
+        assert!(towels.arrangements("ubwu", 10).is_empty());
+    }
+
+    #[test]
+    fn reports_unsupported_colors() {
+        let input = "r, wr, b\n\nbrwrr\nbxyrx\nzz\n";
+        assert_eq!(
+            read_input(input).err(),
+            Some(UnsupportedColors {
+                line: 4,
+                colors: vec!['x', 'y']
+            })
+        );
+        assert!(part_one(input).is_err());
+
+        let input = "r, wr, b\n\nbr,wr\nb r\n";
+        assert_eq!(
+            read_input(input).err(),
+            Some(UnsupportedColors {
+                line: 3,
+                colors: vec![',']
+            })
+        );
+    }
+
+    #[test]
+    fn reads_crlf_line_endings() {
+        let input = crate::template::read_file("examples", DAY).replace('\n', "\r\n");
+        // `lines` only strips the `\r` followed by `\n`.
+        let input = format!("{input}\r");
+        assert_eq!(part_one(&input), Ok(6));
+        assert_eq!(part_two(&input), Ok(16));
+    }
+}
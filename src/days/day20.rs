@@ -0,0 +1,289 @@
+use crate::template::{param, Day, ParamKind, Parameter};
+use rayon::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// The day solved by this module.
+pub const DAY: Day = crate::day!(20);
+
+pub const PARAMETERS: &[Parameter] = &[
+    Parameter {
+        name: "shortcut",
+        default: "100",
+        kind: ParamKind::Count,
+        description: "minimum number of picoseconds a cheat must save",
+    },
+    Parameter {
+        name: "cheat_len_1",
+        default: "2",
+        kind: ParamKind::Count,
+        description: "maximum duration of a cheat in part 1",
+    },
+    Parameter {
+        name: "cheat_len_2",
+        default: "20",
+        kind: ParamKind::Count,
+        description: "maximum duration of a cheat in part 2",
+    },
+];
+
+/// Marks a wall in the index of the track.
+const WALL: usize = usize::MAX;
+
+/// The race track, as the ordered cells of its single path from start to end.
+#[derive(Debug, Clone)]
+pub struct Track {
+    width: usize,
+    height: usize,
+    /// Cells from `S` to `E`.
+    path: Vec<(usize, usize)>,
+    /// Position on the path of each cell, `y * width + x`, `WALL` off the track.
+    index: Vec<usize>,
+}
+
+/// Numbers of cheats by duration and picoseconds saved. Cheats saving nothing are not counted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Savings {
+    /// Longest duration of the cheats counted.
+    max_len: usize,
+    /// Exclusive upper bound of the savings, the length of the path.
+    width: usize,
+    /// Number of cheats lasting `len` and saving `saving`, at `len * width + saving`.
+    counts: Vec<usize>,
+}
+
+impl Savings {
+    /// Number of cheats by picoseconds saved, for cheats lasting at most `cheat_len`.
+    pub fn histogram(&self, cheat_len: usize) -> Vec<usize> {
+        let mut histogram = vec![0; self.width];
+        for by_saving in self
+            .counts
+            .chunks(self.width)
+            .take(cheat_len.min(self.max_len) + 1)
+        {
+            histogram
+                .iter_mut()
+                .zip(by_saving)
+                .for_each(|(h, c)| *h += c);
+        }
+        histogram
+    }
+
+    /// Number of cheats lasting at most `cheat_len` and saving at least `shortcut` picoseconds.
+    pub fn count(&self, cheat_len: usize, shortcut: usize) -> usize {
+        self.histogram(cheat_len).iter().skip(shortcut.max(1)).sum()
+    }
+}
+
+impl Track {
+    pub fn read(input: &str) -> Self {
+        let lines: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+        let (width, height) = (lines[0].len(), lines.len());
+        let find = |c| {
+            (0..height)
+                .find_map(|y| lines[y].iter().position(|b| *b == c).map(|x| (x, y)))
+                .unwrap_or_else(|| panic!("no {} on the track", c as char))
+        };
+        let (start, end) = (find(b'S'), find(b'E'));
+
+        let mut index = vec![WALL; width * height];
+        let mut path = vec![start];
+        index[start.1 * width + start.0] = 0;
+        let mut current = start;
+        while current != end {
+            let (x, y) = current;
+            current = [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+                .into_iter()
+                .find(|&(x, y)| lines[y][x] != b'#' && index[y * width + x] == WALL)
+                .expect("the track is a single path from start to end");
+            index[current.1 * width + current.0] = path.len();
+            path.push(current);
+        }
+
+        Self {
+            width,
+            height,
+            path,
+            index,
+        }
+    }
+
+    /// Picoseconds to race from start to end without cheating.
+    pub fn len(&self) -> usize {
+        self.path.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.path.len() <= 1
+    }
+
+    /// Every cheat lasting at most `max_len`, counted by duration and saving.
+    ///
+    /// A cheat from the `i`-th cell of the path lands on a later cell `j` within the Manhattan
+    /// diamond of radius `max_len` around it, and saves `j - i` minus its duration.
+    pub fn savings(&self, max_len: usize) -> Savings {
+        let width = self.path.len();
+        let offsets: Vec<(isize, isize, usize)> = (-(max_len as isize)..=max_len as isize)
+            .flat_map(|dy| {
+                let reach = (max_len - dy.unsigned_abs()) as isize;
+                (-reach..=reach).map(move |dx| (dx, dy, (dx.abs() + dy.abs()) as usize))
+            })
+            .filter(|(_, _, len)| *len >= 2)
+            .collect();
+
+        let threads = rayon::current_num_threads();
+        let counts = (0..width)
+            .collect::<Vec<_>>()
+            .par_chunks(width.div_ceil(threads))
+            .map(|chunk| {
+                let mut counts = vec![0; (max_len + 1) * width];
+                for &i in chunk {
+                    let (x, y) = self.path[i];
+                    for &(dx, dy, len) in &offsets {
+                        let (x, y) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
+                        if x >= self.width || y >= self.height {
+                            continue;
+                        }
+                        let j = self.index[y * self.width + x];
+                        if j != WALL && j > i + len {
+                            counts[len * width + j - i - len] += 1;
+                        }
+                    }
+                }
+                counts
+            })
+            .reduce(
+                || vec![0; (max_len + 1) * width],
+                |mut a, b| {
+                    a.iter_mut().zip(b).for_each(|(a, b)| *a += b);
+                    a
+                },
+            );
+
+        Savings {
+            max_len,
+            width,
+            counts,
+        }
+    }
+}
+
+pub fn count_shortcuts_over(input: &str, shortcut: usize, cheet_len: usize) -> usize {
+    Track::read(input)
+        .savings(cheet_len)
+        .count(cheet_len, shortcut)
+}
+
+thread_local! {
+    /// The savings of the last input, shared by both parts.
+    static SAVINGS: RefCell<Option<(String, Rc<Savings>)>> = const { RefCell::new(None) };
+}
+
+/// The savings of the cheats on the track of `input` lasting up to the longest duration of both
+/// parts, computed once for both.
+fn savings(input: &str) -> Rc<Savings> {
+    let max_len = param("cheat_len_1", 2).max(param("cheat_len_2", 20));
+    SAVINGS.with_borrow_mut(|cache| match cache {
+        Some((cached, savings)) if cached == input && savings.max_len >= max_len => savings.clone(),
+        _ => {
+            let savings = Rc::new(Track::read(input).savings(max_len));
+            *cache = Some((input.to_string(), savings.clone()));
+            savings
+        }
+    })
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    Some(savings(input).count(param("cheat_len_1", 2), param("shortcut", 100)))
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    Some(savings(input).count(param("cheat_len_2", 20), param("shortcut", 100)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::with_params;
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(
+            count_shortcuts_over(&crate::template::read_file("examples", DAY), 64, 2),
+            1
+        );
+        assert_eq!(
+            count_shortcuts_over(&crate::template::read_file("examples", DAY), 2, 2),
+            44
+        );
+        let result = with_params(&[("shortcut", "20")], || {
+            part_one(&crate::template::read_file("examples", DAY))
+        });
+        assert_eq!(result, Some(5));
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(
+            count_shortcuts_over(&crate::template::read_file("examples", DAY), 76, 20),
+            3
+        );
+        assert_eq!(
+            count_shortcuts_over(&crate::template::read_file("examples", DAY), 72, 20),
+            29
+        );
+        let result = with_params(&[("shortcut", "74"), ("cheat_len_2", "20")], || {
+            part_two(&crate::template::read_file("examples", DAY))
+        });
+        assert_eq!(result, Some(7));
+    }
+
+    #[test]
+    fn shares_savings_between_parts() {
+        let input = crate::template::read_file("examples", DAY);
+        let first = savings(&input);
+        assert_eq!(first.max_len, 20);
+        assert!(Rc::ptr_eq(&first, &savings(&input)));
+
+        let longer = with_params(&[("cheat_len_1", "30")], || savings(&input));
+        assert_eq!(longer.max_len, 30);
+        assert_eq!(longer.histogram(20), first.histogram(20));
+    }
+
+    #[test]
+    fn counts_savings_by_cheat_length() {
+        let track = Track::read(&crate::template::read_file("examples", DAY));
+        assert_eq!(track.len(), 84);
+
+        let savings = track.savings(20);
+        let histogram = savings.histogram(2);
+        let listed: Vec<(usize, usize)> = (0..histogram.len())
+            .filter(|s| histogram[*s] > 0)
+            .map(|s| (s, histogram[s]))
+            .collect();
+        assert_eq!(
+            listed,
+            [
+                (2, 14),
+                (4, 14),
+                (6, 2),
+                (8, 4),
+                (10, 2),
+                (12, 3),
+                (20, 1),
+                (36, 1),
+                (38, 1),
+                (40, 1),
+                (64, 1)
+            ]
+        );
+
+        let histogram = savings.histogram(20);
+        let listed: Vec<usize> = (50..=76).step_by(2).map(|s| histogram[s]).collect();
+        assert_eq!(
+            listed,
+            [32, 31, 29, 39, 25, 23, 20, 19, 12, 14, 12, 22, 4, 3]
+        );
+        assert_eq!(savings.count(20, 50), 285);
+    }
+}
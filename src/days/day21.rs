@@ -0,0 +1,435 @@
+use crate::template::{param, Day, ParamKind, Parameter};
+use rustc_hash::FxHashMap;
+use std::collections::hash_map::Entry;
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+
+/// The day solved by this module.
+pub const DAY: Day = crate::day!(21);
+
+pub const PARAMETERS: &[Parameter] = &[Parameter {
+    name: "sequences",
+    default: "false",
+    kind: ParamKind::Flag,
+    description: "print a shortest sequence of buttons for each code to stderr, when short enough",
+}];
+
+/// Longest sequence of buttons built by [`shortest_sequence`] from the parameters.
+const MAX_SEQUENCE_LEN: usize = 100_000;
+
+/// A keypad, along with the key the arm of the robot typing on it starts on.
+pub struct Keyboard {
+    keys: FxHashMap<(usize, usize), char>,
+    init: (usize, usize),
+}
+impl Keyboard {
+    /// Reads a keypad drawn as lines of keys, `#` marking gaps. The arm starts on `A`.
+    pub fn from_layout(layout: &str) -> Option<Self> {
+        let keys: FxHashMap<(usize, usize), char> = layout
+            .lines()
+            .enumerate()
+            .flat_map(move |(j, l)| l.chars().enumerate().map(move |(i, c)| ((i, j), c)))
+            .filter(|(_, c)| '#' != *c)
+            .collect();
+        let init = *keys.iter().find(|(_, c)| **c == 'A')?.0;
+
+        Some(Self { keys, init })
+    }
+
+    pub fn doors_keyboard() -> Self {
+        Self::from_layout("789\n456\n123\n#0A").unwrap()
+    }
+
+    pub fn robot_keyboard() -> Self {
+        Self::from_layout("#^A\n<v>").unwrap()
+    }
+
+    fn get_key(&self, key: char) -> Result<(usize, usize), SequenceError> {
+        self.keys
+            .iter()
+            .find(|(_, c)| **c == key)
+            .map(|(pos, _)| *pos)
+            .ok_or(SequenceError::UnknownKey(key))
+    }
+
+    /// The keys typed on this keypad by a robot following the arrows of `seq`, `None` if the arm
+    /// goes over a gap or out of the keypad.
+    pub fn convert_back(&self, seq: &str) -> Option<String> {
+        let mut result: Vec<char> = Vec::new();
+        let mut current = self.init;
+        for c in seq.chars() {
+            current = match c {
+                '<' => (current.0.checked_sub(1)?, current.1),
+                '>' => (current.0 + 1, current.1),
+                '^' => (current.0, current.1.checked_sub(1)?),
+                'v' => (current.0, current.1 + 1),
+                'A' => {
+                    result.push(self.keys[&current]);
+                    current
+                }
+                _ => return None,
+            };
+            if !self.keys.contains_key(&current) {
+                return None;
+            }
+        }
+
+        Some(String::from_iter(result))
+    }
+
+    /// The keys next to `pos`, with the arrow moving the arm to each of them.
+    fn neighbours(&self, pos: (usize, usize)) -> impl Iterator<Item = (char, (usize, usize))> + '_ {
+        [
+            ('<', pos.0.checked_sub(1).map(|x| (x, pos.1))),
+            ('>', Some((pos.0 + 1, pos.1))),
+            ('^', pos.1.checked_sub(1).map(|y| (pos.0, y))),
+            ('v', Some((pos.0, pos.1 + 1))),
+        ]
+        .into_iter()
+        .filter_map(|(arrow, next)| Some((arrow, next.filter(|p| self.keys.contains_key(p))?)))
+    }
+
+    /// Every shortest sequence of arrows moving the arm from `init` to `end` without going over a
+    /// gap, none if `end` cannot be reached.
+    fn possible_moves(&self, init: (usize, usize), end: (usize, usize)) -> Vec<String> {
+        // ~ BFS from the end: the moves then follow the keys getting closer to it.
+        let mut dist = FxHashMap::default();
+        dist.insert(end, 0);
+        let mut queue = VecDeque::from([end]);
+        while let Some(pos) = queue.pop_front() {
+            let d = dist[&pos] + 1;
+            for (_, next) in self.neighbours(pos) {
+                if let Entry::Vacant(entry) = dist.entry(next) {
+                    entry.insert(d);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let mut moves = vec![];
+        if dist.contains_key(&init) {
+            self.collect_moves(init, &dist, &mut String::new(), &mut moves);
+        }
+        moves
+    }
+
+    fn collect_moves(
+        &self,
+        pos: (usize, usize),
+        dist: &FxHashMap<(usize, usize), usize>,
+        current: &mut String,
+        moves: &mut Vec<String>,
+    ) {
+        let d = dist[&pos];
+        if d == 0 {
+            moves.push(current.clone());
+            return;
+        }
+        for (arrow, next) in self.neighbours(pos) {
+            if dist.get(&next) == Some(&(d - 1)) {
+                current.push(arrow);
+                self.collect_moves(next, dist, current, moves);
+                current.pop();
+            }
+        }
+    }
+}
+
+/// The keypads between the door and the human: the door keypad is typed on by a robot driven from
+/// the first robot keypad, and so on, the last one being typed on by the human.
+pub fn keypads(robots: usize) -> Vec<Keyboard> {
+    std::iter::once(Keyboard::doors_keyboard())
+        .chain((0..robots).map(|_| Keyboard::robot_keyboard()))
+        .collect()
+}
+
+type Memo = FxHashMap<(usize, char, char), usize>;
+
+/// Number of buttons the human presses for `keypads[level]` to receive `seq`.
+fn seq_cost(
+    keypads: &[Keyboard],
+    level: usize,
+    seq: &str,
+    memo: &mut Memo,
+) -> Result<usize, SequenceError> {
+    let Some(keypad) = keypads.get(level) else {
+        return Ok(seq.len());
+    };
+    let mut from = keypad.keys[&keypad.init];
+    seq.chars()
+        .map(|to| {
+            let cost = key_cost(keypads, level, from, to, memo);
+            from = to;
+            cost
+        })
+        .sum()
+}
+
+/// Number of buttons the human presses to move the arm over `keypads[level]` from `from` to
+/// `to` and press it. The arms of the following keypads start and end on their `A` key.
+fn key_cost(
+    keypads: &[Keyboard],
+    level: usize,
+    from: char,
+    to: char,
+    memo: &mut Memo,
+) -> Result<usize, SequenceError> {
+    if let Some(cost) = memo.get(&(level, from, to)) {
+        return Ok(*cost);
+    }
+    let keypad = &keypads[level];
+    let mut cost = None;
+    for m in keypad.possible_moves(keypad.get_key(from)?, keypad.get_key(to)?) {
+        let c = seq_cost(keypads, level + 1, &(m + "A"), memo)?;
+        cost = Some(cost.map_or(c, |cost: usize| cost.min(c)));
+    }
+    let cost = cost.ok_or(SequenceError::Unreachable(from, to))?;
+    memo.insert((level, from, to), cost);
+    Ok(cost)
+}
+
+/// Builds the buttons pressed by the human for `keypads[level]` to receive `seq`, moving the arm
+/// of each keypad the cheapest way.
+fn build_sequence(
+    keypads: &[Keyboard],
+    level: usize,
+    seq: &str,
+    memo: &mut Memo,
+    out: &mut String,
+) -> Result<(), SequenceError> {
+    let Some(keypad) = keypads.get(level) else {
+        out.push_str(seq);
+        return Ok(());
+    };
+    let mut from = keypad.keys[&keypad.init];
+    for to in seq.chars() {
+        let mut moves: Vec<String> = keypad
+            .possible_moves(keypad.get_key(from)?, keypad.get_key(to)?)
+            .into_iter()
+            .map(|m| m + "A")
+            .collect();
+        moves.sort();
+        let mut best: Option<(usize, String)> = None;
+        for m in moves {
+            let cost = seq_cost(keypads, level + 1, &m, memo)?;
+            if best.as_ref().is_none_or(|(best, _)| cost < *best) {
+                best = Some((cost, m));
+            }
+        }
+        let (_, best) = best.ok_or(SequenceError::Unreachable(from, to))?;
+        build_sequence(keypads, level + 1, &best, memo, out)?;
+        from = to;
+    }
+    Ok(())
+}
+
+/// Error returned when building a sequence of buttons for a code.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SequenceError {
+    /// A keypad does not have a key it has to type.
+    UnknownKey(char),
+    /// The arm cannot move between two keys of a keypad without going over a gap.
+    Unreachable(char, char),
+    /// The shortest sequence has the given length, above the limit.
+    TooLong(usize),
+    /// The sequence built types another code, the keypads are not navigable.
+    Mismatch(String),
+}
+
+impl Display for SequenceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SequenceError::UnknownKey(key) => write!(f, "no key `{key}` on the keypad"),
+            SequenceError::Unreachable(from, to) => {
+                write!(f, "no way from key `{from}` to key `{to}` around the gaps")
+            }
+            SequenceError::TooLong(len) => write!(f, "the shortest sequence has {len} buttons"),
+            SequenceError::Mismatch(code) => write!(f, "the sequence types `{code}` instead"),
+        }
+    }
+}
+
+/// Number of buttons the human presses at least to type `code` through the keypads.
+pub fn presses(keypads: &[Keyboard], code: &str) -> Result<usize, SequenceError> {
+    seq_cost(keypads, 0, code, &mut Memo::default())
+}
+
+/// One of the shortest sequences of buttons the human presses to type `code` through the keypads,
+/// checked by decoding it back through every keypad.
+pub fn shortest_sequence(
+    keypads: &[Keyboard],
+    code: &str,
+    max_len: usize,
+) -> Result<String, SequenceError> {
+    let mut memo = Memo::default();
+    let len = seq_cost(keypads, 0, code, &mut memo)?;
+    if len > max_len {
+        return Err(SequenceError::TooLong(len));
+    }
+
+    let mut seq = String::with_capacity(len);
+    build_sequence(keypads, 0, code, &mut memo, &mut seq)?;
+
+    match decode(keypads, &seq) {
+        Some(decoded) if decoded == code => Ok(seq),
+        decoded => Err(SequenceError::Mismatch(decoded.unwrap_or_default())),
+    }
+}
+
+/// The code typed on the first keypad when the human presses `seq`, `None` if an arm goes over a gap.
+pub fn decode(keypads: &[Keyboard], seq: &str) -> Option<String> {
+    keypads
+        .iter()
+        .rev()
+        .try_fold(seq.to_string(), |seq, keypad| keypad.convert_back(&seq))
+}
+
+fn input_code(code: &str, depth: usize) -> Result<usize, SequenceError> {
+    presses(&keypads(depth), code)
+}
+
+fn complexity(code: &str, depth: usize) -> Result<usize, SequenceError> {
+    let code = code.trim();
+    let first_non_digit = code
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(code.len());
+    let val_code = code[0..first_non_digit].parse::<usize>().unwrap();
+
+    Ok(val_code * input_code(code, depth)?)
+}
+
+/// Number of robot keypads between the door and the human in `part`.
+fn depth(part: u8) -> usize {
+    match part {
+        1 => 2,
+        _ => 25,
+    }
+}
+
+/// Prints a shortest sequence of buttons for each code to stderr, if selected by the parameters.
+pub fn inspect(input: &str, part: u8) {
+    if !param("sequences", false) {
+        return;
+    }
+    let keypads = keypads(depth(part));
+    for code in input.lines().map(str::trim) {
+        match shortest_sequence(&keypads, code, MAX_SEQUENCE_LEN) {
+            Ok(seq) => eprintln!("{code}: {seq}"),
+            Err(e) => eprintln!("{code}: {e}"),
+        }
+    }
+}
+
+pub fn part_one(input: &str) -> Result<usize, SequenceError> {
+    input.lines().map(|code| complexity(code, depth(1))).sum()
+}
+
+pub fn part_two(input: &str) -> Result<usize, SequenceError> {
+    input.lines().map(|code| complexity(code, depth(2))).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(complexity("029A", 2), Ok(68 * 29));
+
+        assert_eq!(complexity("379A", 2), Ok(64 * 379));
+
+        let result = part_one(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Ok(126384));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Ok(154115708116294));
+    }
+
+    #[test]
+    fn builds_shortest_sequences() {
+        let seq = shortest_sequence(&keypads(0), "029A", 100).unwrap();
+        assert_eq!(seq.len(), "<A^A>^^AvvvA".len());
+        assert_eq!(
+            Keyboard::doors_keyboard().convert_back(&seq).unwrap(),
+            "029A"
+        );
+
+        for code in crate::template::read_file("examples", DAY).lines() {
+            for depth in 0..=4 {
+                let seq = shortest_sequence(&keypads(depth), code, 100_000).unwrap();
+                assert_eq!(Ok(seq.len()), input_code(code, depth));
+                assert_eq!(decode(&keypads(depth), &seq).as_deref(), Some(code));
+            }
+        }
+
+        let seq = shortest_sequence(&keypads(2), "029A", 100).unwrap();
+        assert_eq!(seq.len(), 68);
+    }
+
+    #[test]
+    fn rejects_sequences() {
+        assert_eq!(
+            shortest_sequence(&keypads(25), "029A", 1_000_000),
+            Err(SequenceError::TooLong(input_code("029A", 25).unwrap()))
+        );
+        assert_eq!(
+            shortest_sequence(&keypads(2), "02B", 1_000),
+            Err(SequenceError::UnknownKey('B'))
+        );
+        // from A, two steps left hit the gap of the door keypad.
+        assert_eq!(Keyboard::doors_keyboard().convert_back("<<A"), None);
+        assert_eq!(Keyboard::robot_keyboard().convert_back("^"), None);
+    }
+
+    #[test]
+    fn supports_custom_keypads() {
+        // a phone keypad with the gap on the right of 0, driven through a wider arrow keypad.
+        let phone = Keyboard::from_layout("123\n456\n789\nA0#").unwrap();
+        let arrows = Keyboard::from_layout("<^>A\n#v##").unwrap();
+        let keypads = [phone, arrows, Keyboard::robot_keyboard()];
+
+        let seq = shortest_sequence(&keypads, "0A906", 10_000).unwrap();
+        assert_eq!(Ok(seq.len()), presses(&keypads, "0A906"));
+        assert_eq!(decode(&keypads, &seq).as_deref(), Some("0A906"));
+
+        assert!(Keyboard::from_layout("123").is_none());
+    }
+
+    #[test]
+    fn moves_around_gaps() {
+        // 1 and 2 are only linked through the bottom row, A through 4.
+        let keypad = Keyboard::from_layout("1#2\n345\n#A#").unwrap();
+        assert_eq!(
+            presses(&[keypad], "12A"),
+            Ok("^<^A".len() + "v>>^A".len() + "v<vA".len())
+        );
+
+        let keypads = [
+            Keyboard::from_layout("1#2\n345\n#A#").unwrap(),
+            Keyboard::robot_keyboard(),
+            Keyboard::robot_keyboard(),
+        ];
+        let seq = shortest_sequence(&keypads, "2A15", 10_000).unwrap();
+        assert_eq!(Ok(seq.len()), presses(&keypads, "2A15"));
+        assert_eq!(decode(&keypads, &seq).as_deref(), Some("2A15"));
+    }
+
+    #[test]
+    fn rejects_unreachable_and_missing_keys() {
+        let islands = [Keyboard::from_layout("1#A").unwrap()];
+        assert_eq!(
+            shortest_sequence(&islands, "1A", 100),
+            Err(SequenceError::Unreachable('A', '1'))
+        );
+
+        // the robot keypad has no `>` to move right on the door keypad.
+        let keypads = [
+            Keyboard::doors_keyboard(),
+            Keyboard::from_layout("#^A\n<v#").unwrap(),
+        ];
+        assert_eq!(presses(&keypads, "0A"), Err(SequenceError::UnknownKey('>')));
+    }
+}
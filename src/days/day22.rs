@@ -0,0 +1,267 @@
+use crate::template::Day;
+use rayon::prelude::*;
+use rustc_hash::FxHashMap;
+use std::ops::Mul;
+
+/// The day solved by this module.
+pub const DAY: Day = crate::day!(22);
+
+fn mix(secret: usize, value: usize) -> usize {
+    secret ^ value
+}
+fn prune(secret: usize) -> usize {
+    secret % 16777216
+}
+pub fn update_secret(secret: usize) -> usize {
+    let mut secret = prune(mix(secret, secret << 6));
+    secret = prune(mix(secret, secret >> 5));
+    prune(mix(secret, secret << 11))
+}
+
+/// A linear map over GF(2)^24, such as [`update_secret`] whose mixes are XORs of shifts and
+/// prunes are masks. Stored by columns: bit `i` of `columns[j]` is the coefficient at row `i`,
+/// column `j`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BitMatrix {
+    columns: [u32; 24],
+}
+
+impl BitMatrix {
+    pub fn identity() -> Self {
+        Self {
+            columns: std::array::from_fn(|j| 1 << j),
+        }
+    }
+
+    /// The matrix of a linear map, from the images of the basis vectors.
+    pub fn of(f: impl Fn(usize) -> usize) -> Self {
+        Self {
+            columns: std::array::from_fn(|j| f(1 << j) as u32),
+        }
+    }
+
+    /// The matrix of [`update_secret`].
+    pub fn secret_step() -> Self {
+        Self::of(update_secret)
+    }
+
+    pub fn apply(&self, v: usize) -> usize {
+        self.columns
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| v >> j & 1 == 1)
+            .fold(0, |acc, (_, column)| acc ^ *column as usize)
+    }
+
+    pub fn pow(&self, mut n: u64) -> Self {
+        let (mut result, mut square) = (Self::identity(), *self);
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result * square;
+            }
+            square = square * square;
+            n >>= 1;
+        }
+        result
+    }
+
+    /// Number of applications bringing `v` back to itself, found with baby steps and giant steps
+    /// of 2^12 applications. `None` if `v` never comes back, the matrix not being invertible.
+    pub fn cycle_length(&self, v: usize) -> Option<usize> {
+        const STEPS: usize = 1 << 12;
+
+        // baby steps: `self^j v` for `j < STEPS`.
+        let mut baby: FxHashMap<usize, usize> = FxHashMap::from_iter([(v, 0)]);
+        let mut x = v;
+        for j in 1..STEPS {
+            x = self.apply(x);
+            if x == v {
+                return Some(j);
+            }
+            baby.insert(x, j);
+        }
+
+        // giant steps: `self^(i STEPS) v = self^j v` gives `self^(i STEPS - j) v = v`.
+        let giant = self.pow(STEPS as u64);
+        let mut y = v;
+        for i in 1..=STEPS {
+            y = giant.apply(y);
+            if let Some(j) = baby.get(&y) {
+                let k = i * STEPS - j;
+                return (self.pow(k as u64).apply(v) == v).then_some(k);
+            }
+        }
+        None
+    }
+
+    /// Smallest `k > 0` such that `self^k` is the identity, the cycle length of every secret
+    /// dividing it. `None` if the matrix is not invertible.
+    pub fn order(&self) -> Option<usize> {
+        fn gcd(a: usize, b: usize) -> usize {
+            if b == 0 {
+                a
+            } else {
+                gcd(b, a % b)
+            }
+        }
+
+        (0..24).try_fold(1, |order, j| {
+            let length = self.cycle_length(1 << j)?;
+            Some(order / gcd(order, length) * length)
+        })
+    }
+}
+
+impl Mul for BitMatrix {
+    type Output = BitMatrix;
+
+    /// The composition applying `rhs` first.
+    fn mul(self, rhs: BitMatrix) -> BitMatrix {
+        BitMatrix {
+            columns: rhs.columns.map(|column| self.apply(column as usize) as u32),
+        }
+    }
+}
+
+/// The secret a buyer generates after `n` updates of `secret`, in O(log n).
+pub fn nth_secret(secret: usize, n: u64) -> usize {
+    BitMatrix::secret_step().pow(n).apply(secret)
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let mut secrets: Vec<usize> = input.lines().map(|l| l.parse().unwrap()).collect();
+
+    for _ in 0..2000 {
+        secrets = secrets.into_iter().map(update_secret).collect();
+    }
+
+    Some(secrets.into_iter().sum())
+}
+
+/// Number of sequences of four price changes, each between -9 and 9.
+const WINDOWS: usize = 19 * 19 * 19 * 19;
+
+/// Bananas bought per window of four changes summed over the buyers, with `seen` holding for each
+/// window the stamp of the last buyer it was seen for, so that only the first sale of each buyer counts.
+fn add_sales(totals: &mut [u32], seen: &mut [u32], stamp: u32, mut secret: usize) {
+    let mut window = 0;
+    let mut price = secret % 10;
+    for i in 0..2000 {
+        secret = update_secret(secret);
+        let next_price = secret % 10;
+        window = (window * 19 + 9 + next_price - price) % WINDOWS;
+        price = next_price;
+
+        if i >= 3 && seen[window] != stamp {
+            seen[window] = stamp;
+            totals[window] += price as u32;
+        }
+    }
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let secrets: Vec<usize> = input.lines().map(|l| l.parse().unwrap()).collect();
+
+    // one pair of arrays per worker rather than per rayon job.
+    let chunk_len = secrets.len().div_ceil(rayon::current_num_threads()).max(1);
+    let totals = secrets
+        .par_chunks(chunk_len)
+        .map(|chunk| {
+            let (mut totals, mut seen) = (vec![0_u32; WINDOWS], vec![0_u32; WINDOWS]);
+            for (i, secret) in chunk.iter().enumerate() {
+                add_sales(&mut totals, &mut seen, i as u32 + 1, *secret);
+            }
+            totals
+        })
+        .reduce(
+            || vec![0; WINDOWS],
+            |mut acc, totals| {
+                acc.iter_mut().zip(totals).for_each(|(a, t)| *a += t);
+                acc
+            },
+        );
+
+    totals.into_iter().max().map(|t| t as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustc_hash::FxHashSet;
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(update_secret(123), 15887950);
+        assert_eq!(update_secret(15887950), 16495136);
+        let result = part_one(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Some(37327623));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two("1\n2\n3\n2024");
+        assert_eq!(result, Some(23));
+    }
+
+    #[test]
+    fn jumps_to_nth_secret() {
+        let mut secret = 123;
+        for n in 0..=2000 {
+            assert_eq!(nth_secret(123, n), secret);
+            secret = update_secret(secret);
+        }
+        assert_eq!(nth_secret(1, 2000), 8685429);
+
+        let step = BitMatrix::secret_step();
+        assert_eq!(step.pow(5) * step.pow(7), step.pow(12));
+        assert_eq!(step.pow(0), BitMatrix::identity());
+    }
+
+    #[test]
+    fn finds_cycle_length() {
+        let step = BitMatrix::secret_step();
+        assert_eq!(step.cycle_length(0), Some(1));
+        assert_eq!(step.cycle_length(123), Some(16_777_215));
+        assert_eq!(step.order(), Some(16_777_215));
+        assert_eq!(nth_secret(123, 16_777_215), 123);
+
+        // an involution, and a projection which is not invertible.
+        let swap = BitMatrix::of(|v| (v & 0xfff) << 12 | v >> 12);
+        assert_eq!(swap.order(), Some(2));
+        assert_eq!(swap.cycle_length(0x001001), Some(1));
+        let project = BitMatrix::of(|v| v & 0xff);
+        assert_eq!(project.cycle_length(0x100), None);
+        assert_eq!(project.order(), None);
+    }
+
+    /// Best total of bananas, keeping the first price of each buyer per sequence of changes in a map.
+    fn reference_best_price(secrets: &[usize]) -> usize {
+        let mut totals: FxHashMap<[isize; 4], usize> = FxHashMap::default();
+        for secret in secrets {
+            let prices: Vec<isize> =
+                std::iter::successors(Some(*secret), |s| Some(update_secret(*s)))
+                    .take(2001)
+                    .map(|s| (s % 10) as isize)
+                    .collect();
+            let mut seen: FxHashSet<[isize; 4]> = FxHashSet::default();
+            for w in prices.windows(5) {
+                let changes = [w[1] - w[0], w[2] - w[1], w[3] - w[2], w[4] - w[3]];
+                if seen.insert(changes) {
+                    *totals.entry(changes).or_default() += w[4] as usize;
+                }
+            }
+        }
+        totals.into_values().max().unwrap()
+    }
+
+    #[test]
+    fn matches_reference() {
+        let secrets: Vec<usize> = (0..50).map(|i| i * 7919 + 3).collect();
+        let input = secrets
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(part_two(&input), Some(reference_best_price(&secrets)));
+    }
+}
@@ -0,0 +1,328 @@
+use crate::template::Day;
+use itertools::Itertools;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// The day solved by this module.
+pub const DAY: Day = crate::day!(23);
+
+pub fn read_interco<'i>(input: &'i str) -> FxHashSet<(&'i str, &'i str)> {
+    let mut result: FxHashSet<(&'i str, &'i str)> = Default::default();
+    for (c1, c2) in input.lines().map(|l| l.split_once('-').unwrap()) {
+        let c1 = c1.trim();
+        let c2 = c2.trim();
+        assert_ne!(c1, c2);
+        if c1 < c2 {
+            result.insert((c1, c2));
+        } else {
+            result.insert((c2, c1));
+        }
+    }
+    result
+}
+
+/// The network with computers interned as indices, in the order of their names.
+pub struct Network<'i> {
+    names: Vec<&'i str>,
+    ids: FxHashMap<&'i str, usize>,
+    /// Sorted neighbours of each computer.
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl<'i> Network<'i> {
+    pub fn from_edges(edges: &FxHashSet<(&'i str, &'i str)>) -> Self {
+        let names: Vec<&str> = edges
+            .iter()
+            .flat_map(|(c1, c2)| [*c1, *c2])
+            .sorted()
+            .dedup()
+            .collect();
+        let ids: FxHashMap<&str, usize> = names.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+
+        let mut neighbours = vec![vec![]; names.len()];
+        for (c1, c2) in edges {
+            let (id1, id2) = (ids[c1], ids[c2]);
+            neighbours[id1].push(id2);
+            neighbours[id2].push(id1);
+        }
+        neighbours.iter_mut().for_each(|n| n.sort_unstable());
+
+        Self {
+            names,
+            ids,
+            neighbours,
+        }
+    }
+
+    pub fn read(input: &'i str) -> Self {
+        Self::from_edges(&read_interco(input))
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn name(&self, id: usize) -> &'i str {
+        self.names[id]
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn neighbours(&self, id: usize) -> &[usize] {
+        &self.neighbours[id]
+    }
+
+    pub fn linked(&self, id1: usize, id2: usize) -> bool {
+        self.neighbours[id1].binary_search(&id2).is_ok()
+    }
+
+    fn names_of(&self, ids: &[usize]) -> Vec<&'i str> {
+        ids.iter().map(|id| self.names[*id]).collect()
+    }
+
+    /// Every group of `k` computers all linked to each other and accepted by `filter`, each with
+    /// its names sorted.
+    pub fn k_cliques(&self, k: usize, filter: impl Fn(&[&str]) -> bool) -> Vec<Vec<&'i str>> {
+        fn extend(
+            network: &Network,
+            k: usize,
+            clique: &mut Vec<usize>,
+            candidates: &[usize],
+            found: &mut Vec<Vec<usize>>,
+        ) {
+            if clique.len() == k {
+                found.push(clique.clone());
+                return;
+            }
+            // only extend with greater ids, so that each clique is built once.
+            for (i, c) in candidates.iter().enumerate() {
+                let next: Vec<usize> = candidates[i + 1..]
+                    .iter()
+                    .filter(|o| network.linked(*c, **o))
+                    .copied()
+                    .collect();
+                if clique.len() + 1 + next.len() >= k {
+                    clique.push(*c);
+                    extend(network, k, clique, &next, found);
+                    clique.pop();
+                }
+            }
+        }
+
+        let mut found = vec![];
+        let all: Vec<usize> = (0..self.len()).collect();
+        extend(self, k, &mut vec![], &all, &mut found);
+
+        found
+            .iter()
+            .map(|clique| self.names_of(clique))
+            .filter(|names| filter(names))
+            .collect()
+    }
+
+    /// Every group of computers all linked to each other that no other computer could join,
+    /// with the Bron–Kerbosch algorithm, pivoting on the computer with the most candidate neighbours.
+    pub fn maximal_cliques(&self) -> Vec<Vec<&'i str>> {
+        fn bron_kerbosch(
+            network: &Network,
+            clique: &mut Vec<usize>,
+            mut candidates: FxHashSet<usize>,
+            mut excluded: FxHashSet<usize>,
+            found: &mut Vec<Vec<usize>>,
+        ) {
+            if candidates.is_empty() && excluded.is_empty() {
+                found.push(clique.clone());
+                return;
+            }
+
+            let pivot = candidates
+                .iter()
+                .chain(&excluded)
+                .max_by_key(|u| {
+                    network.neighbours[**u]
+                        .iter()
+                        .filter(|n| candidates.contains(n))
+                        .count()
+                })
+                .copied()
+                .unwrap();
+
+            let to_visit: Vec<usize> = candidates
+                .iter()
+                .filter(|v| !network.linked(pivot, **v))
+                .copied()
+                .sorted()
+                .collect();
+            for v in to_visit {
+                let neighbours = &network.neighbours[v];
+                clique.push(v);
+                bron_kerbosch(
+                    network,
+                    clique,
+                    neighbours
+                        .iter()
+                        .filter(|n| candidates.contains(n))
+                        .copied()
+                        .collect(),
+                    neighbours
+                        .iter()
+                        .filter(|n| excluded.contains(n))
+                        .copied()
+                        .collect(),
+                    found,
+                );
+                clique.pop();
+                candidates.remove(&v);
+                excluded.insert(v);
+            }
+        }
+
+        let mut found = vec![];
+        bron_kerbosch(
+            self,
+            &mut vec![],
+            (0..self.len()).collect(),
+            FxHashSet::default(),
+            &mut found,
+        );
+
+        found
+            .into_iter()
+            .map(|mut clique| {
+                clique.sort_unstable();
+                self.names_of(&clique)
+            })
+            .sorted()
+            .collect()
+    }
+
+    /// The largest group of computers all linked to each other, the first by name on ties.
+    pub fn maximum_clique(&self) -> Vec<&'i str> {
+        self.maximal_cliques()
+            .into_iter()
+            .max_by(|a, b| a.len().cmp(&b.len()).then_with(|| b.cmp(a)))
+            .unwrap_or_default()
+    }
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let network = Network::read(input);
+    let with_t = |clique: &[&str]| clique.iter().any(|c| c.starts_with('t'));
+    Some(network.k_cliques(3, with_t).len())
+}
+
+pub fn part_two(input: &str) -> Option<String> {
+    Some(Network::read(input).maximum_clique().join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Some(7));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Some("co,de,ka,ta".to_string()));
+    }
+
+    #[test]
+    fn interns_computers() {
+        let input = crate::template::read_file("examples", DAY);
+        let network = Network::read(&input);
+        assert_eq!(network.len(), 16);
+
+        let ka = network.id("ka").unwrap();
+        let neighbours: Vec<&str> = network
+            .neighbours(ka)
+            .iter()
+            .map(|n| network.name(*n))
+            .collect();
+        assert_eq!(neighbours, vec!["co", "de", "ta", "tb"]);
+        assert!(network.linked(ka, network.id("co").unwrap()));
+        assert_eq!(network.id("zz"), None);
+    }
+
+    /// Random names over a few letters, so that some start with `t`.
+    fn random_network(state: &mut u64, n: usize, density: u64) -> String {
+        let mut next = || {
+            *state ^= *state << 13;
+            *state ^= *state >> 7;
+            *state ^= *state << 17;
+            *state
+        };
+        let names: Vec<String> = (0..n)
+            .map(|i| {
+                format!(
+                    "{}{}",
+                    ['a', 't', 'k', 'z'][i % 4],
+                    (b'a' + i as u8) as char
+                )
+            })
+            .collect();
+        names
+            .iter()
+            .tuple_combinations()
+            .filter(|_| next() % 100 < density)
+            .map(|(a, b)| format!("{a}-{b}"))
+            .join("\n")
+    }
+
+    /// Every subset of computers all linked to each other, by brute force.
+    fn brute_force_cliques<'i>(network: &Network<'i>) -> Vec<Vec<&'i str>> {
+        (0..1_usize << network.len())
+            .map(|set| {
+                (0..network.len())
+                    .filter(|i| set >> i & 1 == 1)
+                    .collect_vec()
+            })
+            .filter(|ids| {
+                ids.iter()
+                    .tuple_combinations()
+                    .all(|(a, b)| network.linked(*a, *b))
+            })
+            .map(|ids| ids.iter().map(|id| network.name(*id)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn finds_cliques_of_random_networks() {
+        let mut state = 0x2023_1223;
+        for round in 0..40 {
+            let input = random_network(&mut state, 6 + round % 8, 30 + round as u64 % 5 * 10);
+            let network = Network::read(&input);
+            let cliques = brute_force_cliques(&network);
+
+            let size = cliques.iter().map(|c| c.len()).max().unwrap();
+            assert_eq!(network.maximum_clique().len(), size, "{input}");
+
+            let maximal: Vec<Vec<&str>> = cliques
+                .iter()
+                .filter(|c| {
+                    !cliques
+                        .iter()
+                        .any(|o| o.len() > c.len() && c.iter().all(|n| o.contains(n)))
+                })
+                .cloned()
+                .sorted()
+                .collect();
+            assert_eq!(network.maximal_cliques(), maximal, "{input}");
+
+            for k in 2..=4 {
+                let with_t = |c: &[&str]| c.iter().any(|n| n.starts_with('t'));
+                let expected = cliques.iter().filter(|c| c.len() == k && with_t(c)).count();
+                assert_eq!(network.k_cliques(k, with_t).len(), expected, "{input}");
+            }
+        }
+    }
+}
@@ -0,0 +1,999 @@
+use crate::template::{param, Day, ParamKind, Parameter};
+use itertools::Itertools;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::fmt::{Display, Formatter, Write};
+use std::fs;
+
+/// The day solved by this module.
+pub const DAY: Day = crate::day!(24);
+
+pub const PARAMETERS: &[Parameter] = &[
+    Parameter {
+        name: "dot",
+        default: "",
+        kind: ParamKind::Text,
+        description:
+            "path to export the circuit as Graphviz DOT in part 2, suspected swapped wires in red",
+    },
+    Parameter {
+        name: "cone",
+        default: "",
+        kind: ParamKind::Text,
+        description: "only export the gates feeding this output wire, e.g. `z12`",
+    },
+    Parameter {
+        name: "explain",
+        default: "false",
+        kind: ParamKind::Flag,
+        description: "print the rules of the adder broken by each bit in part 2 to stderr",
+    },
+];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Gate<'a> {
+    pub entries: [&'a str; 2],
+    pub output: &'a str,
+    pub op: &'a str,
+}
+
+pub fn read_gate(s: &str) -> Gate<'_> {
+    let Some((entry1, op, entry2, _arrow, output)) = s.split_whitespace().collect_tuple() else {
+        unreachable!("invalidate gate line")
+    };
+    Gate {
+        entries: [entry1.trim(), entry2.trim()],
+        output: output.trim(),
+        op: op.trim(),
+    }
+}
+
+pub fn read_status_and_gates(input: &str) -> (FxHashMap<&str, bool>, Vec<Gate<'_>>) {
+    let mut statuses: FxHashMap<&str, bool> = FxHashMap::default();
+    let mut gates: Vec<Gate> = vec![];
+
+    for l in input.lines() {
+        if l.contains(":") {
+            let (input, status) = l.split_once(": ").unwrap();
+            statuses.insert(input, status == "1");
+        }
+        if l.contains("->") {
+            gates.push(read_gate(l));
+        }
+    }
+    (statuses, gates)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Op {
+    And,
+    Or,
+    Xor,
+}
+
+/// A gate of a compiled circuit, operating on wire indices.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Instruction {
+    op: Op,
+    entries: [usize; 2],
+    output: usize,
+}
+
+/// An error which can be returned when compiling or evaluating a circuit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitError {
+    /// A gate with an operation other than AND, OR and XOR.
+    UnknownOp(String),
+    /// Outputs of the gates on a loop, or depending on one.
+    Cycle(Vec<String>),
+    /// An input wire without initial value.
+    MissingInput(String),
+}
+
+impl Display for CircuitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitError::UnknownOp(op) => write!(f, "unknown gate operation `{op}`"),
+            CircuitError::Cycle(wires) => {
+                write!(f, "the circuit loops through {}", wires.join(","))
+            }
+            CircuitError::MissingInput(wire) => write!(f, "no initial value for input {wire}"),
+        }
+    }
+}
+
+/// A circuit compiled into instructions over wire indices, ordered so that each gate comes after
+/// those producing its entries. Wires hold `u64` words, simulating 64 assignments of the inputs
+/// at once: bit `k` of each word is the value of the wire in the `k`-th assignment.
+#[derive(Debug, Clone)]
+pub struct Circuit<'a> {
+    wires: Vec<&'a str>,
+    /// Wires not output by any gate, sorted by name.
+    inputs: Vec<usize>,
+    /// `z` wires, least significant first.
+    outputs: Vec<usize>,
+    instructions: Vec<Instruction>,
+}
+
+impl<'a> Circuit<'a> {
+    pub fn compile(gates: &[Gate<'a>]) -> Result<Self, CircuitError> {
+        let mut wires: Vec<&'a str> = vec![];
+        let mut indices: FxHashMap<&'a str, usize> = FxHashMap::default();
+        let mut index = |wire: &'a str| {
+            *indices.entry(wire).or_insert_with(|| {
+                wires.push(wire);
+                wires.len() - 1
+            })
+        };
+
+        let instructions = gates
+            .iter()
+            .map(|g| {
+                let op = match g.op {
+                    "AND" => Op::And,
+                    "OR" => Op::Or,
+                    "XOR" => Op::Xor,
+                    op => return Err(CircuitError::UnknownOp(op.to_string())),
+                };
+                Ok(Instruction {
+                    op,
+                    entries: g.entries.map(&mut index),
+                    output: index(g.output),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Kahn's algorithm: a gate is ready once the gates producing its entries have been ordered.
+        let mut producer: Vec<Option<usize>> = vec![None; wires.len()];
+        let mut consumers: Vec<Vec<usize>> = vec![vec![]; wires.len()];
+        for (i, instr) in instructions.iter().enumerate() {
+            producer[instr.output] = Some(i);
+            for entry in instr.entries {
+                consumers[entry].push(i);
+            }
+        }
+        let mut pending: Vec<usize> = instructions
+            .iter()
+            .map(|instr| {
+                instr
+                    .entries
+                    .iter()
+                    .filter(|e| producer[**e].is_some())
+                    .count()
+            })
+            .collect();
+
+        let mut ready: Vec<usize> = (0..instructions.len())
+            .filter(|i| pending[*i] == 0)
+            .collect();
+        let mut ordered = Vec::with_capacity(instructions.len());
+        while let Some(i) = ready.pop() {
+            ordered.push(instructions[i]);
+            for &consumer in &consumers[instructions[i].output] {
+                pending[consumer] -= 1;
+                if pending[consumer] == 0 {
+                    ready.push(consumer);
+                }
+            }
+        }
+
+        if ordered.len() < instructions.len() {
+            let looping = instructions
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| pending[*i] > 0)
+                .map(|(_, instr)| wires[instr.output].to_string())
+                .sorted()
+                .collect();
+            return Err(CircuitError::Cycle(looping));
+        }
+
+        let sorted_wires = |keep: &dyn Fn(usize) -> bool| {
+            (0..wires.len())
+                .filter(|w| keep(*w))
+                .sorted_by_key(|w| wires[*w])
+                .collect()
+        };
+        let inputs = sorted_wires(&|w| producer[w].is_none());
+        let outputs = sorted_wires(&|w| wires[w].starts_with('z'));
+
+        Ok(Self {
+            wires,
+            inputs,
+            outputs,
+            instructions: ordered,
+        })
+    }
+
+    /// Names of the input wires, in the order [`Circuit::simulate`] expects their words.
+    pub fn inputs(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.inputs.iter().map(|w| self.wires[*w])
+    }
+
+    /// Names of the `z` wires, least significant first.
+    pub fn outputs(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.outputs.iter().map(|w| self.wires[*w])
+    }
+
+    /// Evaluates the circuit for the words of the inputs, returns the words of the outputs.
+    pub fn simulate(&self, inputs: &[u64]) -> Vec<u64> {
+        let mut values = vec![0; self.wires.len()];
+        for (wire, value) in self.inputs.iter().zip(inputs) {
+            values[*wire] = *value;
+        }
+
+        for instr in &self.instructions {
+            let [a, b] = instr.entries.map(|e| values[e]);
+            values[instr.output] = match instr.op {
+                Op::And => a & b,
+                Op::Or => a | b,
+                Op::Xor => a ^ b,
+            };
+        }
+
+        self.outputs.iter().map(|w| values[*w]).collect()
+    }
+}
+
+/// The number formed by the output words in the given assignment.
+pub fn lane_number(outputs: &[u64], lane: u32) -> usize {
+    outputs
+        .iter()
+        .rev()
+        .fold(0, |acc, word| acc * 2 + (word >> lane & 1) as usize)
+}
+
+/// Evaluates the circuit from the initial wire values, returns the number formed by the `z` wires.
+pub fn compute(init: &FxHashMap<&str, bool>, gates: &[Gate]) -> Result<usize, CircuitError> {
+    let circuit = Circuit::compile(gates)?;
+
+    let inputs = circuit
+        .inputs()
+        .map(|wire| match init.get(wire) {
+            Some(value) => Ok(if *value { u64::MAX } else { 0 }),
+            None => Err(CircuitError::MissingInput(wire.to_string())),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(lane_number(&circuit.simulate(&inputs), 0))
+}
+
+pub fn part_one(input: &str) -> Result<usize, CircuitError> {
+    let (statuses, gates) = read_status_and_gates(input);
+
+    compute(&statuses, &gates)
+}
+
+/// A rule of the ripple-carry adder template broken by the circuit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// The bit position whose gates break the rule.
+    pub bit: usize,
+    pub rule: String,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "bit {:02}: {}", self.bit, self.rule)
+    }
+}
+
+/// The circuit could not be turned into an adder by swapping gate outputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepairError {
+    /// Rules broken by the circuit, which no search over local swaps repairs.
+    pub violations: Vec<Violation>,
+}
+
+impl Display for RepairError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.violations.first() {
+            Some(violation) => write!(f, "no swaps of outputs repair {violation}"),
+            None => f.write_str("the circuit is already an adder"),
+        }
+    }
+}
+
+/// An error which can be returned when repairing the adder of part 2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdderError {
+    Repair(RepairError),
+    Circuit(CircuitError),
+    /// The repaired circuit computes `z` for `x + y`.
+    WrongAddition {
+        x: usize,
+        y: usize,
+        z: usize,
+    },
+}
+
+impl Display for AdderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdderError::Repair(e) => e.fmt(f),
+            AdderError::Circuit(e) => e.fmt(f),
+            AdderError::WrongAddition { x, y, z } => {
+                write!(f, "the repaired circuit computes {x} + {y} = {z}")
+            }
+        }
+    }
+}
+
+fn wire(prefix: char, bit: usize) -> String {
+    format!("{prefix}{bit:02}")
+}
+
+/// Number of bits of the numbers added, i.e. of `x` wires.
+fn input_bits(gates: &[Gate]) -> usize {
+    gates
+        .iter()
+        .flat_map(|g| g.entries)
+        .filter(|e| e.starts_with('x'))
+        .unique()
+        .count()
+}
+
+/// Gates indexed by operation and entries, and by output.
+struct Wiring<'a> {
+    by_entries: FxHashMap<(&'a str, [&'a str; 2]), &'a str>,
+    by_output: FxHashMap<&'a str, Gate<'a>>,
+}
+
+impl<'a> Wiring<'a> {
+    fn new(gates: &[Gate<'a>]) -> Self {
+        let mut by_entries = FxHashMap::default();
+        let mut by_output = FxHashMap::default();
+        for gate in gates {
+            let mut entries = gate.entries;
+            entries.sort_unstable();
+            by_entries.insert((gate.op, entries), gate.output);
+            by_output.insert(gate.output, *gate);
+        }
+        Self {
+            by_entries,
+            by_output,
+        }
+    }
+
+    fn find(&self, op: &str, entry1: &str, entry2: &str) -> Option<&'a str> {
+        let entries = if entry1 <= entry2 {
+            [entry1, entry2]
+        } else {
+            [entry2, entry1]
+        };
+        self.by_entries.get(&(op, entries)).copied()
+    }
+}
+
+/// Matches the gates of `bit` against the full adder template
+/// `z = (x XOR y) XOR carry` and `carry' = (x AND y) OR ((x XOR y) AND carry)`,
+/// the half adder `z = x XOR y` and `carry' = x AND y` for the first bit.
+/// Returns the carry out of the bit when it could be identified.
+fn check_bit<'a>(
+    wiring: &Wiring<'a>,
+    bits: usize,
+    bit: usize,
+    carry: Option<&'a str>,
+    violations: &mut Vec<Violation>,
+) -> Option<&'a str> {
+    let (x, y, z) = (wire('x', bit), wire('y', bit), wire('z', bit));
+    let mut violate = |rule: String| violations.push(Violation { bit, rule });
+
+    let sum = wiring.find("XOR", &x, &y);
+    let generate = wiring.find("AND", &x, &y);
+    if sum.is_none() {
+        violate(format!("no gate computes {x} XOR {y}"));
+    }
+    if generate.is_none() {
+        violate(format!("no gate computes {x} AND {y}"));
+    }
+
+    let carry_out = if bit == 0 {
+        if let Some(sum) = sum.filter(|s| *s != z) {
+            violate(format!("{x} XOR {y} outputs to {sum}, expected {z}"));
+        }
+        generate?
+    } else {
+        for (op, out) in [("XOR", sum), ("AND", generate)] {
+            if let Some(out) = out.filter(|o| o.starts_with('z')) {
+                violate(format!(
+                    "{x} {op} {y} outputs to {out}, expected an internal wire"
+                ));
+            }
+        }
+
+        let z_gate = wiring.by_output.get(z.as_str());
+        match z_gate {
+            None => violate(format!("no gate outputs to {z}")),
+            Some(gate) if gate.op != "XOR" => violate(format!(
+                "{z} is the output of an {} gate, expected XOR",
+                gate.op
+            )),
+            Some(gate) => {
+                if let Some(sum) = sum.filter(|s| !gate.entries.contains(s)) {
+                    violate(format!("{z} does not take {x} XOR {y} ({sum}) as input"));
+                }
+                if let Some(carry) = carry.filter(|c| !gate.entries.contains(c)) {
+                    violate(format!(
+                        "{z} does not take the carry out of bit {:02} ({carry}) as input",
+                        bit - 1
+                    ));
+                }
+            }
+        }
+
+        // when the previous bit is broken, the carry can still be found next to the sum.
+        let carry = carry.or_else(|| {
+            let gate = z_gate.filter(|g| g.op == "XOR")?;
+            let sum = sum.filter(|s| gate.entries.contains(s))?;
+            gate.entries.into_iter().find(|e| *e != sum)
+        });
+        let (sum, carry) = (sum?, carry?);
+
+        if let Some(out) = wiring.find("XOR", sum, carry).filter(|o| *o != z) {
+            violate(format!("{sum} XOR {carry} outputs to {out}, expected {z}"));
+        }
+        let Some(propagate) = wiring.find("AND", sum, carry) else {
+            violate(format!("no gate computes {sum} AND {carry}"));
+            return None;
+        };
+        if propagate.starts_with('z') {
+            violate(format!(
+                "{sum} AND {carry} outputs to {propagate}, expected an internal wire"
+            ));
+        }
+        let generate = generate?;
+        let Some(carry_out) = wiring.find("OR", generate, propagate) else {
+            violate(format!("no gate computes {generate} OR {propagate}"));
+            return None;
+        };
+        carry_out
+    };
+
+    let last = wire('z', bits);
+    if bit + 1 == bits && carry_out != last {
+        violate(format!(
+            "the carry out {carry_out} is not the last output {last}"
+        ));
+    } else if bit + 1 < bits && carry_out.starts_with('z') {
+        violate(format!(
+            "the carry out outputs to {carry_out}, expected an internal wire"
+        ));
+    }
+    Some(carry_out)
+}
+
+/// Checks every bit of the circuit against the ripple-carry adder template, returns the rules
+/// broken ordered by bit, none if the circuit adds `x` and `y` into `z`.
+pub fn verify_adder(gates: &[Gate]) -> Vec<Violation> {
+    let wiring = Wiring::new(gates);
+    let bits = input_bits(gates);
+
+    let mut violations = vec![];
+    let mut carry = None;
+    for bit in 0..bits {
+        carry = check_bit(&wiring, bits, bit, carry, &mut violations);
+    }
+    violations
+}
+
+/// The circuit with the outputs of each pair of wires swapped.
+pub fn swap_outputs<'a>(gates: &[Gate<'a>], swaps: &[(&'a str, &'a str)]) -> Vec<Gate<'a>> {
+    gates
+        .iter()
+        .map(|gate| {
+            let output = swaps
+                .iter()
+                .find_map(|&(a, b)| match gate.output {
+                    o if o == a => Some(b),
+                    o if o == b => Some(a),
+                    _ => None,
+                })
+                .unwrap_or(gate.output);
+            Gate { output, ..*gate }
+        })
+        .collect()
+}
+
+/// Outputs of the gates within three gates of the inputs of `bit` and of the next bit, and of
+/// those feeding the output of `bit`.
+fn local_wires<'a>(gates: &[Gate<'a>], bit: usize) -> Vec<&'a str> {
+    let z = wire('z', bit);
+    let inputs = [
+        wire('x', bit),
+        wire('y', bit),
+        wire('x', bit + 1),
+        wire('y', bit + 1),
+    ];
+
+    let mut frontier: FxHashSet<&str> = inputs.iter().map(String::as_str).collect();
+    let mut wires: Vec<&'a str> = vec![];
+    for _ in 0..3 {
+        for gate in gates {
+            if gate.entries.iter().any(|e| frontier.contains(e)) && !wires.contains(&gate.output) {
+                wires.push(gate.output);
+            }
+        }
+        frontier.extend(wires.iter().copied());
+    }
+
+    if let Some(gate) = gates.iter().find(|g| g.output == z) {
+        let feeding = gates.iter().filter(|g| gate.entries.contains(&g.output));
+        for output in std::iter::once(gate).chain(feeding).map(|g| g.output) {
+            if !wires.contains(&output) {
+                wires.push(output);
+            }
+        }
+    }
+    wires
+}
+
+/// Finds the fewest swaps of gate outputs turning the circuit into a ripple-carry adder.
+///
+/// Bits are repaired from the least significant one with swaps local to the bit, searched by
+/// iterative deepening on the number of swaps, so no shorter repair of that shape exists. Fails
+/// when no such repair uses at most one swap per bit.
+pub fn repair_adder<'a>(gates: &[Gate<'a>]) -> Result<Vec<(&'a str, &'a str)>, RepairError> {
+    let violations = verify_adder(gates);
+    if violations.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut swaps = vec![];
+    for depth in 1..=input_bits(gates) {
+        if repair_within(gates, depth, &mut swaps) {
+            return Ok(swaps);
+        }
+    }
+    Err(RepairError { violations })
+}
+
+/// Whether at most `depth` swaps, each repairing the lowest broken bit, make the circuit an
+/// adder, the swaps found being pushed to `swaps`.
+fn repair_within<'a>(
+    gates: &[Gate<'a>],
+    depth: usize,
+    swaps: &mut Vec<(&'a str, &'a str)>,
+) -> bool {
+    let Some(bit) = verify_adder(gates).first().map(|v| v.bit) else {
+        return true;
+    };
+    if depth == 0 {
+        return false;
+    }
+
+    // the swaps leaving the fewest rules broken come first.
+    let candidates = local_wires(gates, bit)
+        .into_iter()
+        .tuple_combinations()
+        .map(|(a, b)| {
+            let swapped = swap_outputs(gates, &[(a, b)]);
+            let violations = verify_adder(&swapped);
+            ((a, b), swapped, violations)
+        })
+        .filter(|(_, _, violations)| violations.first().is_none_or(|v| v.bit > bit))
+        .sorted_by_key(|(_, _, violations)| violations.len());
+
+    for (swap, swapped, _) in candidates {
+        swaps.push(swap);
+        if repair_within(&swapped, depth - 1, swaps) {
+            return true;
+        }
+        swaps.pop();
+    }
+    false
+}
+
+fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+/// Simulates additions through the circuit: the extreme operands then `trials` random ones, 64 at
+/// a time. Returns the first addition `(x, y, z)` for which the circuit outputs `z` instead of `x + y`.
+pub fn find_wrong_addition(
+    gates: &[Gate],
+    trials: usize,
+) -> Result<Option<(usize, usize, usize)>, CircuitError> {
+    let circuit = Circuit::compile(gates)?;
+    let max = (1 << input_bits(gates)) - 1;
+
+    let mut state = 0x2024_1224;
+    let additions: Vec<(usize, usize)> = [(0, 0), (max, max), (max, 1), (1, max)]
+        .into_iter()
+        .chain((0..trials).map(|_| {
+            let x = xorshift(&mut state) as usize & max;
+            (x, xorshift(&mut state) as usize & max)
+        }))
+        .collect();
+
+    for batch in additions.chunks(64) {
+        let inputs: Vec<u64> = circuit
+            .inputs()
+            .map(|wire| {
+                let (name, bit) = wire.split_at(1);
+                let Ok(bit) = bit.parse::<usize>() else {
+                    return 0;
+                };
+                batch.iter().enumerate().fold(0, |word, (lane, &(x, y))| {
+                    let operand = match name {
+                        "x" => x,
+                        "y" => y,
+                        _ => 0,
+                    };
+                    word | ((operand >> bit & 1) as u64) << lane
+                })
+            })
+            .collect();
+
+        let outputs = circuit.simulate(&inputs);
+        for (lane, &(x, y)) in (0..).zip(batch) {
+            let z = lane_number(&outputs, lane);
+            if z != x + y {
+                return Ok(Some((x, y, z)));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// The gates whose output eventually reaches `wire`, including the gate producing it.
+pub fn cone<'a>(gates: &[Gate<'a>], wire: &str) -> Vec<Gate<'a>> {
+    let by_output: FxHashMap<&str, &Gate> = gates.iter().map(|g| (g.output, g)).collect();
+
+    let mut seen: FxHashSet<&str> = FxHashSet::default();
+    let mut to_visit = vec![wire];
+    let mut result = vec![];
+    while let Some(wire) = to_visit.pop() {
+        if let Some(gate) = by_output.get(wire) {
+            if seen.insert(gate.output) {
+                result.push(**gate);
+                to_visit.extend(gate.entries);
+            }
+        }
+    }
+    result
+}
+
+/// Renders the gates as a Graphviz DOT graph: one node per input wire and per gate, the latter
+/// named after the wire it outputs. `highlighted` wires and the edges they carry are drawn in red.
+pub fn to_dot(gates: &[Gate], highlighted: &FxHashSet<String>) -> String {
+    let gates: Vec<&Gate> = gates.iter().sorted_by_key(|g| g.output).collect();
+    let outputs: FxHashSet<&str> = gates.iter().map(|g| g.output).collect();
+    let inputs: Vec<&str> = gates
+        .iter()
+        .flat_map(|g| g.entries)
+        .filter(|e| !outputs.contains(e))
+        .unique()
+        .sorted()
+        .collect();
+    let highlight = |wire: &str| {
+        if highlighted.contains(wire) {
+            ", color=red, fontcolor=red, penwidth=2"
+        } else {
+            ""
+        }
+    };
+
+    let mut dot =
+        String::from("digraph circuit {\n    rankdir=LR;\n    node [fontname=\"monospace\"];\n");
+
+    for input in inputs {
+        let _ = writeln!(
+            dot,
+            "    \"{input}\" [shape=box, style=filled, fillcolor=lightblue{}];",
+            highlight(input)
+        );
+    }
+
+    for gate in &gates {
+        let (shape, color) = match gate.op {
+            "AND" => ("invhouse", "lightyellow"),
+            "OR" => ("invtrapezium", "lightgreen"),
+            "XOR" => ("diamond", "lightpink"),
+            _ => ("ellipse", "white"),
+        };
+        let peripheries = if gate.output.starts_with('z') { 2 } else { 1 };
+        let _ = writeln!(
+            dot,
+            "    \"{}\" [label=\"{}\\n{}\", shape={shape}, style=filled, fillcolor={color}, peripheries={peripheries}{}];",
+            gate.output,
+            gate.op,
+            gate.output,
+            highlight(gate.output)
+        );
+    }
+
+    for gate in &gates {
+        for entry in gate.entries {
+            let _ = writeln!(
+                dot,
+                "    \"{entry}\" -> \"{}\" [label=\"{entry}\"{}];",
+                gate.output,
+                highlight(entry)
+            );
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+fn swapped_wires(swaps: &Result<Vec<(&str, &str)>, RepairError>) -> FxHashSet<String> {
+    swaps
+        .iter()
+        .flatten()
+        .flat_map(|&(a, b)| [a.to_string(), b.to_string()])
+        .collect()
+}
+
+/// Explains the broken rules or exports the circuit of part 2, as selected by the parameters.
+pub fn inspect(input: &str, part: u8) {
+    if part != 2 {
+        return;
+    }
+    let (_statuses, gates) = read_status_and_gates(input);
+
+    if param("explain", false) {
+        for violation in verify_adder(&gates) {
+            eprintln!("{violation}");
+        }
+    }
+
+    let dot_path: String = param("dot", String::new());
+    if dot_path.is_empty() {
+        return;
+    }
+    let swapped = swapped_wires(&repair_adder(&gates));
+
+    let wire: String = param("cone", String::new());
+    let gates = if wire.is_empty() {
+        gates
+    } else {
+        cone(&gates, &wire)
+    };
+    if let Err(e) = fs::write(&dot_path, to_dot(&gates, &swapped)) {
+        eprintln!("could not write the circuit to {dot_path}: {e}");
+    }
+}
+
+pub fn part_two(input: &str) -> Result<String, AdderError> {
+    let (_statuses, gates) = read_status_and_gates(input);
+
+    let swaps = repair_adder(&gates);
+    let swapped = swapped_wires(&swaps);
+
+    let swaps = swaps.map_err(AdderError::Repair)?;
+    if let Some((x, y, z)) =
+        find_wrong_addition(&swap_outputs(&gates, &swaps), 1000).map_err(AdderError::Circuit)?
+    {
+        return Err(AdderError::WrongAddition { x, y, z });
+    }
+
+    Ok(swapped.iter().sorted().join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&crate::template::read_file("examples", DAY));
+        assert_eq!(result, Ok(4));
+
+        let result = part_one(&crate::template::read_file_part("examples", DAY, 1));
+        assert_eq!(result, Ok(2024));
+    }
+
+    #[test]
+    fn exports_dot() {
+        let input = crate::template::read_file_part("examples", DAY, 1);
+        let (_, gates) = read_status_and_gates(&input);
+        let highlighted = FxHashSet::from_iter(["z00".to_string()]);
+
+        let dot = to_dot(&gates, &highlighted);
+        assert!(dot.starts_with("digraph circuit {"));
+        assert!(dot.contains("\"x00\" [shape=box"));
+        assert!(dot.contains("\"z00\" [label=\"XOR\\nz00\", shape=diamond, style=filled, fillcolor=lightpink, peripheries=2, color=red"));
+        assert!(dot.contains("\"mjb\" -> \"z00\" [label=\"mjb\"];"));
+        assert_eq!(dot.matches(" -> ").count(), 2 * gates.len());
+    }
+
+    #[test]
+    fn restricts_to_cone() {
+        let input = crate::template::read_file_part("examples", DAY, 1);
+        let (_, gates) = read_status_and_gates(&input);
+
+        let cone = cone(&gates, "mjb");
+        let outputs: FxHashSet<&str> = cone.iter().map(|g| g.output).collect();
+        // mjb = ntg XOR fgs, ntg = x00 XOR y04, fgs = y04 OR y02 in the example.
+        assert_eq!(outputs, FxHashSet::from_iter(["mjb", "ntg", "fgs"]));
+
+        let dot = to_dot(&cone, &FxHashSet::default());
+        assert!(!dot.contains("z01"));
+        assert!(!dot.contains("red"));
+    }
+
+    #[test]
+    fn reports_cycles() {
+        let input = "x00: 1\ny00: 0\n\nx00 AND aaa -> bbb\nbbb OR y00 -> aaa\naaa XOR x00 -> z00\n";
+        let (statuses, gates) = read_status_and_gates(input);
+        assert_eq!(
+            compute(&statuses, &gates),
+            Err(CircuitError::Cycle(vec![
+                "aaa".into(),
+                "bbb".into(),
+                "z00".into()
+            ]))
+        );
+
+        let input = crate::template::read_file("examples", DAY);
+        let (_, gates) = read_status_and_gates(&input);
+        assert_eq!(
+            compute(&FxHashMap::default(), &gates),
+            Err(CircuitError::MissingInput("x00".into()))
+        );
+    }
+
+    #[test]
+    fn simulates_64_assignments() {
+        let input = crate::template::read_file_part("examples", DAY, 1);
+        let (statuses, gates) = read_status_and_gates(&input);
+        let circuit = Circuit::compile(&gates).unwrap();
+
+        // lane k flips the initial value of the input k % 10.
+        let inputs: Vec<u64> = circuit
+            .inputs()
+            .enumerate()
+            .map(|(i, wire)| {
+                let word = if statuses[wire] { u64::MAX } else { 0 };
+                word ^ (0..64).filter(|k| k % 10 == i).fold(0, |w, k| w | 1 << k)
+            })
+            .collect();
+        let outputs = circuit.simulate(&inputs);
+
+        for lane in 0..64 {
+            let mut init = statuses.clone();
+            let flipped = circuit.inputs().nth(lane as usize % 10).unwrap();
+            init.insert(flipped, !statuses[flipped]);
+            assert_eq!(Ok(lane_number(&outputs, lane)), compute(&init, &gates));
+        }
+    }
+
+    /// A ripple-carry adder of `bits` bits with the outputs of `swaps` exchanged.
+    fn adder(bits: usize, swaps: &[(&str, &str)]) -> String {
+        let mut input = String::new();
+        for i in 0..bits {
+            let _ = writeln!(input, "x{i:02}: {}\ny{i:02}: {}", i % 2, i % 3 % 2);
+        }
+        input.push('\n');
+
+        let mut gate = |a: String, op: &str, b: String, out: String| {
+            let out = swaps
+                .iter()
+                .find_map(|&(s1, s2)| match out.as_str() {
+                    o if o == s1 => Some(s2.to_string()),
+                    o if o == s2 => Some(s1.to_string()),
+                    _ => None,
+                })
+                .unwrap_or(out);
+            let _ = writeln!(input, "{a} {op} {b} -> {out}");
+        };
+        gate("x00".into(), "XOR", "y00".into(), "z00".into());
+        gate("x00".into(), "AND", "y00".into(), "c00".into());
+        for i in 1..bits {
+            let (x, y, prev) = (
+                format!("x{i:02}"),
+                format!("y{i:02}"),
+                format!("c{:02}", i - 1),
+            );
+            let carry = if i + 1 == bits {
+                format!("z{bits:02}")
+            } else {
+                format!("c{i:02}")
+            };
+            gate(x.clone(), "XOR", y.clone(), format!("s{i:02}"));
+            gate(y, "AND", x, format!("a{i:02}"));
+            gate(prev.clone(), "XOR", format!("s{i:02}"), format!("z{i:02}"));
+            gate(format!("s{i:02}"), "AND", prev, format!("t{i:02}"));
+            gate(format!("a{i:02}"), "OR", format!("t{i:02}"), carry);
+        }
+        input
+    }
+
+    #[test]
+    fn verifies_adder() {
+        let input = adder(12, &[]);
+        let (statuses, gates) = read_status_and_gates(&input);
+
+        assert_eq!(verify_adder(&gates), vec![]);
+        assert_eq!(find_wrong_addition(&gates, 1000), Ok(None));
+        // x = 0b10101010101, y = 0b100100100100 in the generated inputs.
+        assert_eq!(
+            compute(&statuses, &gates),
+            Ok(0b101010101010 + 0b010010010010)
+        );
+    }
+
+    #[test]
+    fn explains_violations() {
+        let input = adder(12, &[("z05", "c05")]);
+        let (_, gates) = read_status_and_gates(&input);
+
+        let violations: Vec<String> = verify_adder(&gates).iter().map(|v| v.to_string()).collect();
+        assert_eq!(
+            violations,
+            vec![
+                "bit 05: z05 is the output of an OR gate, expected XOR",
+                "bit 05: s05 XOR c04 outputs to c05, expected z05",
+                "bit 05: the carry out outputs to z05, expected an internal wire",
+                "bit 06: z06 does not take the carry out of bit 05 (z05) as input",
+                "bit 06: no gate computes s06 AND z05",
+            ]
+        );
+    }
+
+    #[test]
+    fn repairs_each_swap_pattern() {
+        // swapping the two entries of the carry OR, e.g. `t05` and `a05`, leaves a valid adder.
+        for swap in [
+            ("z05", "t05"),
+            ("z05", "c05"),
+            ("z05", "a05"),
+            ("s05", "a05"),
+            ("s05", "t05"),
+            ("z05", "z06"),
+            ("z11", "c10"),
+        ] {
+            let input = adder(12, &[swap]);
+            let (_, gates) = read_status_and_gates(&input);
+
+            let swaps = repair_adder(&gates).unwrap();
+            let swaps: Vec<_> = swaps.iter().map(|&(a, b)| (a.min(b), a.max(b))).collect();
+            assert_eq!(
+                swaps,
+                vec![(swap.0.min(swap.1), swap.0.max(swap.1))],
+                "{swap:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn repairs_with_fewest_swaps() {
+        let input = adder(12, &[]);
+        let (_, gates) = read_status_and_gates(&input);
+        assert_eq!(repair_adder(&gates), Ok(vec![]));
+
+        let input = adder(12, &[("z02", "t02"), ("s07", "a07")]);
+        let (_, gates) = read_status_and_gates(&input);
+        let swaps = repair_adder(&gates).unwrap();
+        assert_eq!(swaps.len(), 2);
+        assert!(verify_adder(&swap_outputs(&gates, &swaps)).is_empty());
+    }
+
+    #[test]
+    fn finds_wrong_addition() {
+        let input = adder(12, &[("s05", "a05")]);
+        let (_, gates) = read_status_and_gates(&input);
+
+        let (x, y, z) = find_wrong_addition(&gates, 100).unwrap().unwrap();
+        assert_ne!(x + y, z);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = adder(
+            16,
+            &[
+                ("z03", "t03"),
+                ("s06", "a06"),
+                ("z09", "c09"),
+                ("z12", "a12"),
+            ],
+        );
+        let result = part_two(&input);
+        assert_eq!(result, Ok("a06,a12,c09,s06,t03,z03,z09,z12".to_string()));
+    }
+}
@@ -0,0 +1,120 @@
+//! Solutions of every day of advent.
+//!
+//! Each `dayNN` module exposes its `DAY`, its `part_one` and `part_two` functions along with the
+//! building blocks used to solve the puzzle. [`all`] and [`get`] give access to every day through
+//! the uniform [`Solution`] trait.
+use crate::template::{Day, FnSolution, Solution};
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+static SOLUTIONS: [&dyn Solution; 25] = [
+    &FnSolution::new(day01::DAY, day01::part_one, day01::part_two),
+    &FnSolution::new(day02::DAY, day02::part_one, day02::part_two),
+    &FnSolution::new(day03::DAY, day03::part_one, day03::part_two),
+    &FnSolution::new(day04::DAY, day04::part_one, day04::part_two),
+    &FnSolution::new(day05::DAY, day05::part_one, day05::part_two),
+    &FnSolution::new(day06::DAY, day06::part_one, day06::part_two),
+    &FnSolution::new(day07::DAY, day07::part_one, day07::part_two),
+    &FnSolution::new(day08::DAY, day08::part_one, day08::part_two),
+    &FnSolution::new(day09::DAY, day09::part_one, day09::part_two)
+        .with_parameters(day09::PARAMETERS)
+        .with_inspect(day09::inspect),
+    &FnSolution::new(day10::DAY, day10::part_one, day10::part_two),
+    &FnSolution::new(day11::DAY, day11::part_one, day11::part_two)
+        .with_parameters(day11::PARAMETERS),
+    &FnSolution::new(day12::DAY, day12::part_one, day12::part_two)
+        .with_parameters(day12::PARAMETERS)
+        .with_inspect(day12::inspect),
+    &FnSolution::new(day13::DAY, day13::part_one, day13::part_two)
+        .with_parameters(day13::PARAMETERS)
+        .with_inspect(day13::inspect),
+    &FnSolution::new(day14::DAY, day14::part_one, day14::part_two)
+        .with_parameters(day14::PARAMETERS)
+        .with_inspect(day14::inspect),
+    &FnSolution::new(day15::DAY, day15::part_one, day15::part_two)
+        .with_parameters(day15::PARAMETERS)
+        .with_inspect(day15::inspect),
+    &FnSolution::new(day16::DAY, day16::part_one, day16::part_two)
+        .with_parameters(day16::PARAMETERS)
+        .with_inspect(day16::inspect),
+    &FnSolution::new(day17::DAY, day17::part_one, day17::part_two)
+        .with_parameters(day17::PARAMETERS)
+        .with_inspect(day17::inspect),
+    &FnSolution::new(day18::DAY, day18::part_one, day18::part_two)
+        .with_parameters(day18::PARAMETERS),
+    &FnSolution::new(day19::DAY, day19::part_one, day19::part_two),
+    &FnSolution::new(day20::DAY, day20::part_one, day20::part_two)
+        .with_parameters(day20::PARAMETERS),
+    &FnSolution::new(day21::DAY, day21::part_one, day21::part_two)
+        .with_parameters(day21::PARAMETERS)
+        .with_inspect(day21::inspect),
+    &FnSolution::new(day22::DAY, day22::part_one, day22::part_two),
+    &FnSolution::new(day23::DAY, day23::part_one, day23::part_two),
+    &FnSolution::new(day24::DAY, day24::part_one, day24::part_two)
+        .with_parameters(day24::PARAMETERS)
+        .with_inspect(day24::inspect),
+    &FnSolution::new(day25::DAY, day25::part_one, day25::part_two),
+];
+
+/// Every solution, ordered by day.
+pub fn all() -> &'static [&'static dyn Solution] {
+    &SOLUTIONS
+}
+
+/// The solution of the given day.
+pub fn get(day: Day) -> &'static dyn Solution {
+    SOLUTIONS[usize::from(day.into_inner() - 1)]
+}
+
+/// Prints the debug output of `part` of the given day, nothing when the day is not registered.
+pub fn inspect(day: Day, input: &str, part: u8) {
+    if let Some(solution) = all().iter().find(|s| s.day() == day) {
+        solution.inspect(input, part);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::{all_days, read_file};
+
+    #[test]
+    fn solutions_are_ordered_by_day() {
+        for (solution, day) in all().iter().zip(all_days()) {
+            assert_eq!(solution.day(), day);
+            assert_eq!(get(day).day(), day);
+        }
+    }
+
+    #[test]
+    fn runs_parts_through_trait() {
+        let solution = get(day01::DAY);
+        let input = read_file("examples", day01::DAY);
+        assert_eq!(solution.part_one(&input), Ok(Some("11".to_string())));
+        assert_eq!(solution.part_two(&input), Ok(Some("31".to_string())));
+    }
+}
@@ -2,12 +2,11 @@ use advent_of_code::template::commands::{all, download, read, scaffold, solve, t
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::commands::solve::SolutionArgs;
+    use advent_of_code::template::{parse_param, Day, InputSource};
     use std::process;
 
     pub enum AppArguments {
@@ -27,6 +26,8 @@ mod args {
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            watch: bool,
+            solution_args: SolutionArgs,
         },
         All {
             release: bool,
@@ -37,7 +38,9 @@ mod args {
             store: bool,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
@@ -68,14 +71,50 @@ mod args {
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let day = args.free_from_str()?;
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let watch = args.contains("--watch");
+
+                let part = args.opt_value_from_str("--part")?;
+                if part.is_some_and(|p| p != 1 && p != 2) {
+                    return Err("expecting part 1 or 2 after `--part`".into());
+                }
+                let visualize = args.contains("--visualize");
+                let params = args
+                    .values_from_fn("--param", parse_param)?
+                    .into_iter()
+                    .chain(visualize.then(|| ("visualize".to_string(), "true".to_string())))
+                    .collect();
+
+                let input = match args.opt_value_from_str::<_, String>("--input")? {
+                    Some(path) if path == "-" => InputSource::Stdin,
+                    Some(path) => InputSource::File(path.into()),
+                    None if args.contains("--example") => {
+                        InputSource::Example(args.opt_free_from_str()?)
+                    }
+                    None => InputSource::Inputs,
+                };
+
+                AppArguments::Solve {
+                    day,
+                    release,
+                    dhat,
+                    submit,
+                    watch,
+                    solution_args: SolutionArgs {
+                        input,
+                        part,
+                        params,
+                    },
+                }
+            }
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -121,24 +160,11 @@ fn main() {
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                watch,
+                solution_args,
+            } => solve::handle(day, release, dhat, submit, watch, &solution_args),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
-            }
+            AppArguments::Today { wait } => today::handle(wait),
         },
     };
 }
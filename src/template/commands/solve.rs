@@ -1,8 +1,72 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::days;
+use crate::template::{watch, Day, InputSource, Params};
+
+/// Options forwarded to the solution binary.
+#[derive(Debug, Default)]
+pub struct SolutionArgs {
+    pub input: InputSource,
+    pub part: Option<u8>,
+    pub params: Params,
+}
+
+impl SolutionArgs {
+    fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        match &self.input {
+            InputSource::Inputs => {}
+            InputSource::Example(part) => {
+                args.push("--example".to_string());
+                args.extend(part.map(|k| k.to_string()));
+            }
+            InputSource::File(path) => {
+                args.push("--input".to_string());
+                args.push(path.to_string_lossy().to_string());
+            }
+            InputSource::Stdin => {
+                args.push("--input".to_string());
+                args.push("-".to_string());
+            }
+        }
+
+        if let Some(part) = self.part {
+            args.push("--part".to_string());
+            args.push(part.to_string());
+        }
+
+        for key in self.params.keys() {
+            args.push("--param".to_string());
+            args.push(format!(
+                "{key}={}",
+                self.params.raw(key).unwrap_or_default()
+            ));
+        }
+
+        args
+    }
+}
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    watch: bool,
+    solution_args: &SolutionArgs,
+) {
+    check_params(day, &solution_args.params);
+
+    if watch {
+        if solution_args.input == InputSource::Stdin {
+            eprintln!("`--watch` cannot read the input from stdin, use `--input <path>` instead.");
+            process::exit(1);
+        }
+        watch::watch(day, release, &solution_args.to_args());
+        return;
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -18,6 +82,8 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
+    cmd_args.extend(solution_args.to_args());
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
@@ -25,6 +91,7 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
+        .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
@@ -32,3 +99,33 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+/// Parameters are only looked up by solutions, so a typo would silently fall back to the default
+/// and an invalid value would only panic once solving.
+fn check_params(day: Day, params: &Params) {
+    let known = days::get(day).parameters();
+    for key in params.keys() {
+        let value = params.raw(key).unwrap_or_default();
+        match known.iter().find(|p| p.name == key) {
+            Some(parameter) => {
+                if let Err(e) = parameter.kind.check(value) {
+                    eprintln!(
+                        "Error: invalid value `{value}` for parameter `{key}` of day {day}: {e}."
+                    );
+                    process::exit(1);
+                }
+            }
+            None => {
+                let names: Vec<&str> = known.iter().map(|p| p.name).collect();
+                eprintln!(
+                    "Warning: day {day} has no parameter `{key}` (known: {}).",
+                    if names.is_empty() {
+                        "none".to_string()
+                    } else {
+                        names.join(", ")
+                    }
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use std::{io::stdout, process};
+
+use crate::template::commands::{download, read, scaffold};
+use crate::template::unlock::{wait_for_unlock, SystemClock};
+use crate::template::{examples, Day};
+
+pub fn handle(wait: bool) {
+    let day = if wait {
+        wait_for_unlock(&SystemClock, &mut stdout())
+    } else {
+        Day::today()
+    };
+
+    let Some(day) = day else {
+        eprintln!(
+            "`today` command can only be run between the 1st and \
+            the 25th of december. Please use `scaffold` with a specific day."
+        );
+        process::exit(1)
+    };
+
+    scaffold::handle(day, false);
+    download::handle(day);
+
+    match examples::write_example(day) {
+        Ok(Some(path)) => println!("🎄 Successfully wrote example to \"{path}\"."),
+        Ok(None) => println!("🎄 No example extracted, fill \"data/examples/{day}.txt\" manually."),
+        Err(e) => eprintln!("Failed to extract example: {e}"),
+    }
+
+    read::handle(day);
+}
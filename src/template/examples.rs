@@ -0,0 +1,120 @@
+/// Extraction of example inputs from puzzle descriptions downloaded by aoc-cli.
+use std::{fs, io};
+
+use crate::template::Day;
+
+const FENCE: &str = "```";
+
+/// Returns the code blocks of a markdown puzzle description, in order of appearance,
+/// each paired with the prose preceding it.
+pub fn code_blocks(puzzle: &str) -> Vec<(&str, &str)> {
+    let mut blocks = vec![];
+    let mut rest = puzzle;
+
+    while let Some(start) = rest.find(FENCE) {
+        let prose = &rest[..start];
+        let after_fence = &rest[start + FENCE.len()..];
+        // skip the (optional) info string of the fence.
+        let Some(body_start) = after_fence.find('\n') else {
+            break;
+        };
+        let body = &after_fence[body_start + 1..];
+        let Some(end) = body.find(FENCE) else {
+            break;
+        };
+
+        blocks.push((prose, &body[..end]));
+        rest = &body[end + FENCE.len()..];
+    }
+
+    blocks
+}
+
+/// Picks the example input of a puzzle: the first code block introduced by a paragraph mentioning
+/// an example, or the first code block if there is no such paragraph.
+pub fn extract_example(puzzle: &str) -> Option<String> {
+    let blocks = code_blocks(puzzle);
+
+    blocks
+        .iter()
+        .find(|(prose, _)| {
+            let intro = prose.trim_end().rsplit("\n\n").next().unwrap_or_default();
+            intro.to_lowercase().contains("example")
+        })
+        .or(blocks.first())
+        .map(|(_, body)| body.trim_end_matches('\n').to_string())
+        .filter(|example| !example.is_empty())
+}
+
+/// Writes the example of the stored puzzle description to `data/examples/<day>.txt`,
+/// unless that file already holds an example.
+pub fn write_example(day: Day) -> io::Result<Option<String>> {
+    let example_path = format!("data/examples/{day}.txt");
+
+    if fs::read_to_string(&example_path).is_ok_and(|content| !content.trim().is_empty()) {
+        return Ok(None);
+    }
+
+    let puzzle = fs::read_to_string(format!("data/puzzles/{day}.md"))?;
+    match extract_example(&puzzle) {
+        Some(example) => {
+            fs::write(&example_path, example + "\n")?;
+            Ok(Some(example_path))
+        }
+        None => Ok(None),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{code_blocks, extract_example};
+
+    const PUZZLE: &str = "## --- Day 1: Test ---
+
+Some lore with a `0` inline code.
+
+```
+not the example
+```
+
+For example, suppose the list is:
+
+```
+3   4
+4   3
+```
+
+And another block:
+
+```
+1
+```
+";
+
+    #[test]
+    fn finds_all_code_blocks() {
+        let blocks = code_blocks(PUZZLE);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[1].1, "3   4\n4   3\n");
+        assert_eq!(blocks[2].1, "1\n");
+    }
+
+    #[test]
+    fn picks_block_introduced_as_example() {
+        assert_eq!(extract_example(PUZZLE), Some("3   4\n4   3".to_string()));
+    }
+
+    #[test]
+    fn falls_back_to_first_block() {
+        let puzzle = "Intro\n\n```\n#..\n.#.\n```\n";
+        assert_eq!(extract_example(puzzle), Some("#..\n.#.".to_string()));
+    }
+
+    #[test]
+    fn handles_missing_blocks() {
+        assert_eq!(extract_example("no code here"), None);
+        assert_eq!(extract_example("```\nunterminated"), None);
+    }
+}
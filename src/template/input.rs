@@ -0,0 +1,143 @@
+use std::fmt::Display;
+use std::io::Read;
+use std::path::PathBuf;
+use std::{env, fs, io, process};
+
+use crate::template::{read_file, read_file_part, Day};
+
+/// Where a solution binary reads its puzzle input from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The real input, `data/inputs/<day>.txt`.
+    #[default]
+    Inputs,
+    /// `data/examples/<day>.txt`, or `data/examples/<day>-<k>.txt` when a part is given.
+    Example(Option<u8>),
+    /// An arbitrary file.
+    File(PathBuf),
+    Stdin,
+}
+
+/// An error which can be returned when parsing the input source of the command line.
+#[derive(Debug, PartialEq, Eq)]
+pub struct InputSourceError(String);
+
+impl std::error::Error for InputSourceError {}
+
+impl Display for InputSourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl InputSource {
+    /// Parses `--input <path>` (`-` for stdin) or `--example [k]` out of the arguments of a solution binary.
+    pub fn from_args(args: &[String]) -> Result<Self, InputSourceError> {
+        let input = args.iter().position(|a| a == "--input");
+        let example = args.iter().position(|a| a == "--example");
+
+        match (input, example) {
+            (Some(_), Some(_)) => Err(InputSourceError(
+                "`--input` and `--example` cannot be used together".into(),
+            )),
+            (Some(i), None) => match args.get(i + 1).map(String::as_str) {
+                Some("-") => Ok(InputSource::Stdin),
+                Some(path) if !path.starts_with("--") => Ok(InputSource::File(path.into())),
+                _ => Err(InputSourceError(
+                    "expecting a path or `-` after `--input`".into(),
+                )),
+            },
+            (None, Some(i)) => match args.get(i + 1).filter(|a| !a.starts_with("--")) {
+                Some(part) => part
+                    .parse()
+                    .map(|k| InputSource::Example(Some(k)))
+                    .map_err(|_| InputSourceError(format!("invalid example number `{part}`"))),
+                None => Ok(InputSource::Example(None)),
+            },
+            (None, None) => Ok(InputSource::Inputs),
+        }
+    }
+
+    /// Reads the content of the source for the given day.
+    pub fn read(&self, day: Day) -> io::Result<String> {
+        match self {
+            InputSource::Inputs => Ok(read_file("inputs", day)),
+            InputSource::Example(None) => Ok(read_file("examples", day)),
+            InputSource::Example(Some(part)) => Ok(read_file_part("examples", day, *part)),
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// Reads the puzzle input selected on the command line of a solution binary,
+/// the real input if none was selected.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    let args: Vec<String> = env::args().collect();
+
+    let source = InputSource::from_args(&args).unwrap_or_else(|e| {
+        eprintln!("{e}. Format: cargo solve 1 [--input <path> | --input - | --example [k]]");
+        process::exit(1);
+    });
+
+    source.read(day).unwrap_or_else(|e| {
+        eprintln!("could not read input from {source:?}: {e}");
+        process::exit(1);
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::InputSource;
+
+    fn parse(args: &[&str]) -> Result<InputSource, super::InputSourceError> {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        InputSource::from_args(&args)
+    }
+
+    #[test]
+    fn defaults_to_inputs() {
+        assert_eq!(parse(&["target/debug/01"]), Ok(InputSource::Inputs));
+        assert_eq!(
+            parse(&["target/debug/01", "--submit", "1"]),
+            Ok(InputSource::Inputs)
+        );
+    }
+
+    #[test]
+    fn parses_input_file() {
+        assert_eq!(
+            parse(&["01", "--input", "other.txt"]),
+            Ok(InputSource::File("other.txt".into()))
+        );
+        assert_eq!(parse(&["01", "--input", "-"]), Ok(InputSource::Stdin));
+        assert!(parse(&["01", "--input"]).is_err());
+        assert!(parse(&["01", "--input", "--time"]).is_err());
+    }
+
+    #[test]
+    fn parses_example() {
+        assert_eq!(parse(&["01", "--example"]), Ok(InputSource::Example(None)));
+        assert_eq!(
+            parse(&["01", "--example", "--time"]),
+            Ok(InputSource::Example(None))
+        );
+        assert_eq!(
+            parse(&["01", "--example", "2"]),
+            Ok(InputSource::Example(Some(2)))
+        );
+        assert!(parse(&["01", "--example", "two"]).is_err());
+    }
+
+    #[test]
+    fn rejects_both_sources() {
+        assert!(parse(&["01", "--example", "--input", "-"]).is_err());
+    }
+}
@@ -2,13 +2,24 @@ use std::{env, fs};
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod examples;
 pub mod runner;
+#[cfg(feature = "today")]
+pub mod unlock;
+pub mod viewer;
+pub mod watch;
 
 pub use day::*;
+pub use input::*;
+pub use params::*;
+pub use solution::*;
 
 mod day;
+mod input;
+mod params;
 mod readme_benchmarks;
 mod run_multi;
+mod solution;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -39,6 +50,7 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Parts can also be selected at runtime with `--part <1|2>`.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -61,8 +73,14 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = $crate::template::read_input(DAY);
+            $(
+                if is_part_selected($part) {
+                    run_part($func, &input, DAY, $part);
+                    // debug output of the day, out of the timed runs.
+                    $crate::days::inspect(DAY, &input, $part);
+                }
+            )*
         }
     };
 }
@@ -0,0 +1,188 @@
+/// Tunable parameters of solutions, set with `--param key=value` on the command line.
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::{env, process};
+
+/// A set of raw `key=value` parameters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, String>);
+
+/// An error which can be returned when parsing a `key=value` parameter.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParamFromStrError(String);
+
+impl std::error::Error for ParamFromStrError {}
+
+impl Display for ParamFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a parameter as `key=value`, got `{}`", self.0)
+    }
+}
+
+/// Parses a single `key=value` pair.
+pub fn parse_param(s: &str) -> Result<(String, String), ParamFromStrError> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(ParamFromStrError(s.to_string())),
+    }
+}
+
+impl Params {
+    /// Collects every `--param key=value` out of the arguments of a solution binary.
+    pub fn from_args(args: &[String]) -> Result<Self, ParamFromStrError> {
+        args.iter()
+            .zip(args.iter().skip(1))
+            .filter(|(flag, _)| *flag == "--param")
+            .map(|(_, param)| parse_param(param))
+            .collect()
+    }
+
+    pub fn raw(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromIterator<(String, String)> for Params {
+    fn from_iter<T: IntoIterator<Item = (String, String)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+thread_local! {
+    static SCOPED: RefCell<Option<Params>> = const { RefCell::new(None) };
+}
+
+fn command_line_params() -> &'static Params {
+    static PARAMS: OnceLock<Params> = OnceLock::new();
+    PARAMS.get_or_init(|| {
+        let args: Vec<String> = env::args().collect();
+        Params::from_args(&args).unwrap_or_else(|e| {
+            eprintln!("{e}. Format: cargo solve 1 --param key=value");
+            process::exit(1);
+        })
+    })
+}
+
+/// Typed lookup of a parameter, returns `default` when it is not set.
+///
+/// Values come from the parameters in scope of [`with_params`] if any, from `--param key=value`
+/// on the command line otherwise. Parameters are per thread: read them before going parallel.
+pub fn param<T>(key: &str, default: T) -> T
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    let raw = SCOPED.with_borrow(|scoped| match scoped {
+        Some(params) => params.raw(key).map(str::to_string),
+        None => command_line_params().raw(key).map(str::to_string),
+    });
+
+    match raw {
+        Some(raw) => raw
+            .parse()
+            .unwrap_or_else(|e| panic!("invalid value `{raw}` for parameter `{key}`: {e:?}")),
+        None => default,
+    }
+}
+
+/// Runs `f` with the given parameters in place of those of the command line, e.g. to run a
+/// solution against an example which uses smaller dimensions than the real input.
+pub fn with_params<R>(params: &[(&str, &str)], f: impl FnOnce() -> R) -> R {
+    let params = params
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+    // restores the previous parameters even when `f` panics.
+    struct Restore(Option<Params>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED.set(self.0.take());
+        }
+    }
+
+    let _restore = Restore(SCOPED.replace(Some(params)));
+    f()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{param, parse_param, with_params, Params};
+    use crate::template::ParamKind;
+
+    #[test]
+    fn parses_params_from_args() {
+        let args: Vec<String> = ["14", "--param", "w=11", "--time", "--param", "h = 7"]
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+        let params = Params::from_args(&args).unwrap();
+        assert_eq!(params.raw("w"), Some("11"));
+        assert_eq!(params.raw("h"), Some("7"));
+        assert_eq!(params.raw("time"), None);
+    }
+
+    #[test]
+    fn rejects_malformed_params() {
+        assert!(parse_param("w").is_err());
+        assert!(parse_param("=11").is_err());
+        assert_eq!(parse_param("w="), Ok(("w".into(), "".into())));
+    }
+
+    #[test]
+    fn looks_up_typed_params() {
+        assert_eq!(param("w", 101), 101);
+        with_params(&[("w", "11"), ("name", "x")], || {
+            assert_eq!(param("w", 101), 11);
+            assert_eq!(param("h", 103), 103);
+            assert_eq!(param("name", String::new()), "x");
+        });
+        assert_eq!(param("w", 101), 101);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_on_invalid_value() {
+        with_params(&[("w", "wide")], || param("w", 101));
+    }
+
+    #[test]
+    fn restores_params_after_panic() {
+        let result = std::panic::catch_unwind(|| {
+            with_params(&[("w", "11")], || panic!("solution failed"));
+        });
+        assert!(result.is_err());
+        assert_eq!(param("w", 101), 101);
+    }
+
+    #[test]
+    fn checks_values_by_kind() {
+        assert!(ParamKind::Flag.check("true").is_ok());
+        assert!(ParamKind::Flag.check("yes").is_err());
+        assert!(ParamKind::Count.check("101").is_ok());
+        assert!(ParamKind::Count.check("-1").is_err());
+        assert!(ParamKind::Text.check("").is_ok());
+        let detector = ParamKind::OneOf(&["triangle", "variance"]);
+        assert!(detector.check("variance").is_ok());
+        assert_eq!(
+            detector.check("grid"),
+            Err("expecting one of `triangle`, `variance`".to_string())
+        );
+    }
+}
@@ -7,18 +7,52 @@ use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, Day, InputSource, PartResult, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+pub fn run_part<I: Clone, R: PartResult>(func: impl Fn(I) -> R, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples) = run_timed(
+        |input| func(input).into_answer(),
+        input,
+        |result| {
+            print_result(
+                &result.as_ref().ok().and_then(Option::as_ref),
+                &part_str,
+                "",
+            )
+        },
+    );
+
+    print_result(
+        &result.as_ref().ok().and_then(Option::as_ref),
+        &part_str,
+        &format_duration(&duration, samples),
+    );
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    match result {
+        Ok(Some(result)) => {
+            submit_result(result, day, part);
+        }
+        Ok(None) => {}
+        Err(e) => eprintln!("{part_str}: {e}"),
+    }
+}
+
+/// Whether `part` should run, i.e. no other part was selected with `--part`.
+pub fn is_part_selected(part: u8) -> bool {
+    let args: Vec<String> = env::args().collect();
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    let Some(index) = args.iter().position(|x| x == "--part") else {
+        return true;
+    };
+
+    match args.get(index + 1).map(|p| p.parse::<u8>()) {
+        Some(Ok(selected @ (1 | 2))) => selected == part,
+        _ => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --part 1");
+            process::exit(1);
+        }
     }
 }
 
@@ -148,6 +182,11 @@ fn submit_result<T: Display>(
         process::exit(1);
     }
 
+    if InputSource::from_args(&args).is_ok_and(|source| source != InputSource::Inputs) {
+        eprintln!("Only results computed from the real input can be submitted.");
+        process::exit(1);
+    }
+
     let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
 
     let Ok(part_submit) = args[part_index].parse::<u8>() else {
@@ -0,0 +1,148 @@
+/// Uniform access to the solution of a day, independently of the answer types of its parts.
+use std::fmt::Display;
+
+use crate::template::Day;
+
+/// A tunable value of a solution, such as the size of a grid, along with the value used for the puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parameter {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub kind: ParamKind,
+    pub description: &'static str,
+}
+
+/// The values a [`Parameter`] accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    /// `true` or `false`.
+    Flag,
+    /// A non-negative integer.
+    Count,
+    /// Any text, e.g. a path.
+    Text,
+    /// One of the given words.
+    OneOf(&'static [&'static str]),
+}
+
+impl ParamKind {
+    /// Checks `value` before it reaches the solution, whose lookup panics on invalid values.
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        match self {
+            ParamKind::Flag if value.parse::<bool>().is_err() => {
+                Err("expecting `true` or `false`".to_string())
+            }
+            ParamKind::Count if value.parse::<u64>().is_err() => {
+                Err("expecting a non-negative integer".to_string())
+            }
+            ParamKind::OneOf(words) if !words.contains(&value) => {
+                Err(format!("expecting one of `{}`", words.join("`, `")))
+            }
+            // any string is a valid text.
+            ParamKind::Text => Ok(()),
+            ParamKind::Flag | ParamKind::Count | ParamKind::OneOf(_) => Ok(()),
+        }
+    }
+}
+
+/// What a part function returns: its answer, `None` when it has none, or an error telling why.
+pub trait PartResult {
+    type Answer: Display;
+
+    fn into_answer(self) -> Result<Option<Self::Answer>, String>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<Option<T>, String> {
+        Ok(self)
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<Option<T>, String> {
+        self.map(Some).map_err(|e| e.to_string())
+    }
+}
+
+/// The solution of a day of advent.
+pub trait Solution: Sync {
+    /// The day solved.
+    fn day(&self) -> Day;
+
+    /// Parameters the solution can be tuned with, empty if the puzzle has none.
+    fn parameters(&self) -> &'static [Parameter] {
+        &[]
+    }
+
+    fn part_one(&self, input: &str) -> Result<Option<String>, String>;
+
+    fn part_two(&self, input: &str) -> Result<Option<String>, String>;
+
+    /// Debug output of a part, such as traces, renderings or viewers, selected by parameters.
+    /// Runs once after the part was solved and timed, never while benching.
+    fn inspect(&self, _input: &str, _part: u8) {}
+}
+
+/// A [`Solution`] built from the `part_one` and `part_two` functions of a day module.
+pub struct FnSolution<A, B> {
+    day: Day,
+    parameters: &'static [Parameter],
+    part_one: fn(&str) -> A,
+    part_two: fn(&str) -> B,
+    inspect: Option<fn(&str, u8)>,
+}
+
+impl<A, B> FnSolution<A, B> {
+    pub const fn new(day: Day, part_one: fn(&str) -> A, part_two: fn(&str) -> B) -> Self {
+        Self {
+            day,
+            parameters: &[],
+            part_one,
+            part_two,
+            inspect: None,
+        }
+    }
+
+    pub const fn with_parameters(self, parameters: &'static [Parameter]) -> Self {
+        Self { parameters, ..self }
+    }
+
+    pub const fn with_inspect(self, inspect: fn(&str, u8)) -> Self {
+        Self {
+            inspect: Some(inspect),
+            ..self
+        }
+    }
+}
+
+impl<A: PartResult, B: PartResult> Solution for FnSolution<A, B> {
+    fn day(&self) -> Day {
+        self.day
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        self.parameters
+    }
+
+    fn part_one(&self, input: &str) -> Result<Option<String>, String> {
+        (self.part_one)(input)
+            .into_answer()
+            .map(|r| r.map(|r| r.to_string()))
+    }
+
+    fn part_two(&self, input: &str) -> Result<Option<String>, String> {
+        (self.part_two)(input)
+            .into_answer()
+            .map(|r| r.map(|r| r.to_string()))
+    }
+
+    fn inspect(&self, input: &str, part: u8) {
+        if let Some(inspect) = self.inspect {
+            inspect(input, part);
+        }
+    }
+}
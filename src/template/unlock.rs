@@ -0,0 +1,189 @@
+/// Scheduling of the daily puzzle unlock, used by `cargo today --wait`.
+use std::io::Write;
+use std::thread;
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, TimeZone, Utc};
+
+use crate::template::day::SERVER_UTC_OFFSET;
+use crate::template::Day;
+
+/// Puzzles (and inputs) are sometimes not served right at midnight, give the server a moment.
+const UNLOCK_GRACE: Duration = Duration::seconds(2);
+
+/// Source of time for the unlock watcher. Tests provide a fake implementation to avoid real waiting.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The wall clock.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        if let Ok(duration) = duration.to_std() {
+            thread::sleep(duration);
+        }
+    }
+}
+
+/// Returns the next day of advent to unlock strictly after `now`, along with its unlock instant.
+pub fn next_unlock(now: DateTime<Utc>) -> Option<(Day, DateTime<Utc>)> {
+    let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+    let tomorrow = now.with_timezone(&offset).date_naive().succ_opt()?;
+
+    let date = match (tomorrow.month(), tomorrow.day()) {
+        (12, 1..=25) => tomorrow,
+        (12, _) => NaiveDate::from_ymd_opt(tomorrow.year() + 1, 12, 1)?,
+        _ => NaiveDate::from_ymd_opt(tomorrow.year(), 12, 1)?,
+    };
+
+    let unlock = offset
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .single()?
+        .with_timezone(&Utc);
+
+    Some((Day::new(u8::try_from(date.day()).ok()?)?, unlock))
+}
+
+/// Blocks until the next puzzle unlocks, rendering a countdown to `out` every second.
+pub fn wait_for_unlock(clock: &impl Clock, out: &mut impl Write) -> Option<Day> {
+    let (day, unlock) = next_unlock(clock.now())?;
+    let ready_at = unlock + UNLOCK_GRACE;
+
+    loop {
+        let remaining = ready_at - clock.now();
+        if remaining <= Duration::zero() {
+            break;
+        }
+
+        let _ = write!(
+            out,
+            "\r⏳ Day {day} unlocks in {}  ",
+            format_countdown(remaining)
+        );
+        let _ = out.flush();
+
+        clock.sleep(remaining.min(Duration::seconds(1)));
+    }
+
+    let _ = writeln!(out, "\r🔓 Day {day} is unlocked!                    ");
+    Some(day)
+}
+
+fn format_countdown(remaining: Duration) -> String {
+    // round up so that the countdown never displays `00:00:00` while still waiting.
+    let secs = (remaining.num_milliseconds() + 999) / 1000;
+    let (days, secs) = (secs / 86_400, secs % 86_400);
+    let hms = format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    );
+
+    if days > 0 {
+        format!("{days}d {hms}")
+    } else {
+        hms
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::cell::{Cell, RefCell};
+
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+
+    use super::{format_countdown, next_unlock, wait_for_unlock, Clock};
+    use crate::day;
+
+    struct FakeClock {
+        now: Cell<DateTime<Utc>>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeClock {
+        fn at(now: DateTime<Utc>) -> Self {
+            Self {
+                now: Cell::new(now),
+                sleeps: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.sleeps.borrow_mut().push(duration);
+            self.now.set(self.now.get() + duration);
+        }
+    }
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, s).unwrap()
+    }
+
+    #[test]
+    fn unlocks_next_day_during_advent() {
+        // 23:59:00 on Dec 4th server time.
+        let (day, at) = next_unlock(utc(2024, 12, 5, 4, 59, 0)).unwrap();
+        assert_eq!(day, day!(5));
+        assert_eq!(at, utc(2024, 12, 5, 5, 0, 0));
+    }
+
+    #[test]
+    fn unlocks_first_day_before_advent() {
+        let (day, at) = next_unlock(utc(2024, 11, 3, 12, 0, 0)).unwrap();
+        assert_eq!(day, day!(1));
+        assert_eq!(at, utc(2024, 12, 1, 5, 0, 0));
+    }
+
+    #[test]
+    fn unlocks_first_day_on_the_eve() {
+        // still Nov 30th on the server even though it's Dec 1st in UTC.
+        let (day, at) = next_unlock(utc(2024, 12, 1, 3, 0, 0)).unwrap();
+        assert_eq!(day, day!(1));
+        assert_eq!(at, utc(2024, 12, 1, 5, 0, 0));
+    }
+
+    #[test]
+    fn unlocks_next_year_after_advent() {
+        let (day, at) = next_unlock(utc(2024, 12, 25, 5, 0, 0)).unwrap();
+        assert_eq!(day, day!(1));
+        assert_eq!(at, utc(2025, 12, 1, 5, 0, 0));
+    }
+
+    #[test]
+    fn waits_until_unlock() {
+        let clock = FakeClock::at(utc(2024, 12, 5, 4, 59, 57));
+        let mut out = vec![];
+
+        let day = wait_for_unlock(&clock, &mut out);
+
+        assert_eq!(day, Some(day!(5)));
+        assert_eq!(clock.now(), utc(2024, 12, 5, 5, 0, 2));
+        assert_eq!(clock.sleeps.borrow().len(), 5);
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Day 05 unlocks in 00:00:05"));
+        assert!(out.contains("Day 05 unlocks in 00:00:01"));
+        assert!(out.contains("Day 05 is unlocked!"));
+    }
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(Duration::milliseconds(500)), "00:00:01");
+        assert_eq!(format_countdown(Duration::seconds(3723)), "01:02:03");
+        assert_eq!(format_countdown(Duration::seconds(90_061)), "1d 01:01:01");
+    }
+}
@@ -0,0 +1,263 @@
+/// Interactive terminal player for the frames of a solution, used by `--visualize` modes.
+use std::io::{self, stdout, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::style::Print;
+use crossterm::terminal::{
+    self, disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
+    LeaveAlternateScreen,
+};
+use crossterm::{ExecutableCommand, QueueableCommand};
+
+const MAX_SPEED: u32 = 64;
+
+const HELP: &str = "←/→ step  space play/pause  +/- speed  g<n>⏎ jump  q quit";
+
+/// Playback state of the viewer, driven by key presses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub frame: usize,
+    frames: usize,
+    pub playing: bool,
+    /// Frames per second while playing.
+    pub speed: u32,
+    /// Digits typed after `g`, the frame to jump to once Enter is pressed.
+    pub jump: Option<String>,
+}
+
+impl Player {
+    pub fn new(frames: usize, start: usize) -> Self {
+        Self {
+            frame: start.min(frames.saturating_sub(1)),
+            frames,
+            playing: false,
+            speed: 4,
+            jump: None,
+        }
+    }
+
+    fn seek(&mut self, frame: usize) {
+        self.frame = frame.min(self.frames.saturating_sub(1));
+    }
+
+    /// Updates the state for a key press, returns `false` when the viewer should quit.
+    pub fn handle(&mut self, key: KeyCode) -> bool {
+        if let Some(jump) = &mut self.jump {
+            match key {
+                KeyCode::Char(c) if c.is_ascii_digit() => jump.push(c),
+                KeyCode::Backspace => {
+                    jump.pop();
+                }
+                KeyCode::Enter => {
+                    if let Ok(frame) = jump.parse() {
+                        self.seek(frame);
+                    }
+                    self.jump = None;
+                }
+                _ => self.jump = None,
+            }
+            return true;
+        }
+
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.playing = false;
+                self.seek(self.frame + 1);
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.playing = false;
+                self.seek(self.frame.saturating_sub(1));
+            }
+            KeyCode::Char(' ') => self.playing = !self.playing,
+            KeyCode::Char('+') => self.speed = (self.speed * 2).min(MAX_SPEED),
+            KeyCode::Char('-') => self.speed = (self.speed / 2).max(1),
+            KeyCode::Char('g') => self.jump = Some(String::new()),
+            _ => {}
+        }
+        true
+    }
+
+    /// Moves to the next frame while playing, pauses on the last one.
+    pub fn advance(&mut self) {
+        if self.playing {
+            if self.frame + 1 < self.frames {
+                self.frame += 1;
+            } else {
+                self.playing = false;
+            }
+        }
+    }
+
+    pub fn status(&self) -> String {
+        let state = match &self.jump {
+            Some(jump) => format!("jump to: {jump}_"),
+            None if self.playing => format!("▶ {} fps", self.speed),
+            None => "⏸ paused".to_string(),
+        };
+        format!(
+            "frame {}/{}  {state}",
+            self.frame,
+            self.frames.saturating_sub(1)
+        )
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_millis(1000 / u64::from(self.speed))
+    }
+}
+
+/// Restores the terminal, even when rendering panics.
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Self> {
+        enable_raw_mode()?;
+        stdout().execute(EnterAlternateScreen)?.execute(Hide)?;
+        Ok(Screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = stdout()
+            .execute(Show)
+            .and_then(|o| o.execute(LeaveAlternateScreen));
+        let _ = disable_raw_mode();
+    }
+}
+
+/// Shows `frames` frames in the terminal, starting with `start`, until the user quits.
+/// `render` returns the text of a frame, possibly styled, lines separated by `\n`.
+/// Nothing is shown without frames.
+pub fn play(
+    frames: usize,
+    start: usize,
+    mut render: impl FnMut(usize) -> String,
+) -> io::Result<()> {
+    if frames == 0 {
+        return Ok(());
+    }
+    let _screen = Screen::enter()?;
+    let mut out = stdout();
+    let mut player = Player::new(frames, start);
+    let mut next_tick = Instant::now() + player.interval();
+
+    loop {
+        let (_, rows) = terminal::size()?;
+        out.queue(Clear(ClearType::All))?;
+        let text = render(player.frame);
+        for (row, line) in (0..rows.saturating_sub(2)).zip(text.lines()) {
+            out.queue(MoveTo(0, row))?.queue(Print(line))?;
+        }
+        out.queue(MoveTo(0, rows.saturating_sub(2)))?
+            .queue(Print(player.status()))?
+            .queue(MoveTo(0, rows.saturating_sub(1)))?
+            .queue(Print(HELP))?;
+        out.flush()?;
+
+        let timeout = if player.playing {
+            next_tick.saturating_duration_since(Instant::now())
+        } else {
+            Duration::from_secs(3600)
+        };
+
+        if event::poll(timeout)? {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    if !player.handle(key.code) {
+                        return Ok(());
+                    }
+                    next_tick = Instant::now() + player.interval();
+                }
+                _ => {}
+            }
+        } else {
+            player.advance();
+            next_tick = Instant::now() + player.interval();
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use super::Player;
+
+    #[test]
+    fn steps_within_bounds() {
+        let mut player = Player::new(10, 0);
+        player.handle(KeyCode::Left);
+        assert_eq!(player.frame, 0);
+        player.handle(KeyCode::Right);
+        player.handle(KeyCode::Char('l'));
+        assert_eq!(player.frame, 2);
+
+        let mut player = Player::new(10, 42);
+        assert_eq!(player.frame, 9);
+        player.handle(KeyCode::Right);
+        assert_eq!(player.frame, 9);
+    }
+
+    #[test]
+    fn handles_no_frames() {
+        let mut player = Player::new(0, 3);
+        assert_eq!(player.frame, 0);
+        player.handle(KeyCode::Right);
+        player.advance();
+        assert_eq!(player.status(), "frame 0/0  ⏸ paused");
+    }
+
+    #[test]
+    fn plays_and_pauses() {
+        let mut player = Player::new(3, 0);
+        player.advance();
+        assert_eq!(player.frame, 0);
+
+        player.handle(KeyCode::Char(' '));
+        player.advance();
+        player.advance();
+        assert_eq!(player.frame, 2);
+        assert!(player.playing);
+        player.advance();
+        assert!(!player.playing);
+
+        player.handle(KeyCode::Char(' '));
+        player.handle(KeyCode::Left);
+        assert!(!player.playing);
+    }
+
+    #[test]
+    fn changes_speed() {
+        let mut player = Player::new(3, 0);
+        player.handle(KeyCode::Char('+'));
+        assert_eq!(player.speed, 8);
+        for _ in 0..10 {
+            player.handle(KeyCode::Char('-'));
+        }
+        assert_eq!(player.speed, 1);
+    }
+
+    #[test]
+    fn jumps_to_frame() {
+        let mut player = Player::new(10_000, 0);
+        for key in ['g', '7', '5', '2'] {
+            player.handle(KeyCode::Char(key));
+        }
+        assert_eq!(player.status(), "frame 0/9999  jump to: 752_");
+        player.handle(KeyCode::Backspace);
+        player.handle(KeyCode::Enter);
+        assert_eq!(player.frame, 75);
+        assert!(!player.handle(KeyCode::Char('q')));
+
+        player.handle(KeyCode::Char('g'));
+        player.handle(KeyCode::Esc);
+        assert_eq!(player.jump, None);
+        assert_eq!(player.frame, 75);
+    }
+}
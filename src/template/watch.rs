@@ -0,0 +1,350 @@
+/// Watch mode for `cargo solve <day> --watch`: re-runs the example tests and the solution on save.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(300);
+
+const ANSI_GREEN: &str = "\x1b[32m";
+const ANSI_RED: &str = "\x1b[31m";
+
+/// Delays a run until watched files stopped changing for a while, so that a burst of saves only triggers one run.
+pub struct Debouncer {
+    delay: Duration,
+    last_change: Option<Instant>,
+}
+
+impl Debouncer {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            last_change: None,
+        }
+    }
+
+    /// Registers the result of a poll, returns `true` when a run is due.
+    pub fn poll(&mut self, changed: bool, now: Instant) -> bool {
+        if changed {
+            self.last_change = Some(now);
+            return false;
+        }
+
+        match self.last_change {
+            Some(at) if now.duration_since(at) >= self.delay => {
+                self.last_change = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Outcome of `cargo test` for a day.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TestSummary {
+    /// The binary did not compile, holds the compiler errors.
+    BuildFailed(Vec<String>),
+    Ran {
+        passed: usize,
+        failed: usize,
+        failures: Vec<String>,
+    },
+}
+
+impl TestSummary {
+    pub fn is_success(&self) -> bool {
+        matches!(self, TestSummary::Ran { failed: 0, .. })
+    }
+}
+
+/// Files whose modification triggers a new run.
+fn watched_paths(day: Day) -> Vec<PathBuf> {
+    let mut paths = vec![
+        PathBuf::from(format!("src/bin/{day}.rs")),
+        PathBuf::from(format!("src/days/day{day}.rs")),
+        PathBuf::from("src/lib.rs"),
+        PathBuf::from(format!("data/inputs/{day}.txt")),
+    ];
+
+    // examples may be split into several files, e.g. `15.txt` and `15-1.txt`.
+    if let Ok(entries) = fs::read_dir("data/examples") {
+        let mut examples: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(&day.to_string()))
+            })
+            .collect();
+        examples.sort();
+        paths.extend(examples);
+    }
+
+    paths
+}
+
+fn snapshot(day: Day) -> Vec<(PathBuf, Option<SystemTime>)> {
+    watched_paths(day)
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+fn strip_ansi(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip the control sequence up to its final letter.
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
+/// Parses the output of `cargo test`.
+pub fn parse_test_summary(stdout: &str, stderr: &str) -> TestSummary {
+    let Some(result) = stdout.lines().find(|l| l.starts_with("test result:")) else {
+        let errors = stderr
+            .lines()
+            .filter(|l| l.starts_with("error"))
+            .map(str::to_string)
+            .collect();
+        return TestSummary::BuildFailed(errors);
+    };
+
+    let count = |label: &str| {
+        result
+            .split(';')
+            .find_map(|part| part.split_once(label))
+            .and_then(|(n, _)| n.split_whitespace().last()?.parse().ok())
+            .unwrap_or(0)
+    };
+
+    let failures = stdout
+        .lines()
+        .filter_map(|l| l.strip_prefix("test ")?.strip_suffix(" ... FAILED"))
+        .map(str::to_string)
+        .collect();
+
+    TestSummary::Ran {
+        passed: count(" passed"),
+        failed: count(" failed"),
+        failures,
+    }
+}
+
+/// Extracts the `Part N: <answer>` lines printed by the runner.
+pub fn parse_answers(stdout: &str) -> Vec<String> {
+    stdout
+        .lines()
+        // intermediate results are overwritten with a carriage return, keep the final one.
+        .filter_map(|l| Some(strip_ansi(l.rsplit('\r').next()?).trim_end().to_string()))
+        .filter(|l| l.starts_with("Part "))
+        .collect()
+}
+
+fn cargo(args: &[&str], release: bool) -> std::io::Result<Output> {
+    let mut cmd_args = args.to_vec();
+    if release {
+        // before any `--`, whose following arguments go to the binary.
+        cmd_args.insert(1, "--release");
+    }
+    Command::new("cargo").args(&cmd_args).output()
+}
+
+/// Tests of days exposed in the library live in their `days` module, others in their binary.
+fn run_tests(day: Day, release: bool) -> std::io::Result<Output> {
+    if Path::new(&format!("src/days/day{day}.rs")).exists() {
+        cargo(&["test", "--lib", &format!("days::day{day}::")], release)
+    } else {
+        cargo(&["test", "--bin", &day.to_string()], release)
+    }
+}
+
+/// `args` are forwarded to the solution binary, e.g. to select its input.
+fn run_once(day: Day, release: bool, args: &[String], run: usize) {
+    println!("\n{ANSI_BOLD}Day {day}{ANSI_RESET} {ANSI_ITALIC}run #{run}{ANSI_RESET}");
+
+    let tests = match run_tests(day, release) {
+        Ok(output) => parse_test_summary(
+            &String::from_utf8_lossy(&output.stdout),
+            &String::from_utf8_lossy(&output.stderr),
+        ),
+        Err(e) => {
+            eprintln!("Failed to call cargo: {e}");
+            return;
+        }
+    };
+
+    match &tests {
+        TestSummary::BuildFailed(errors) => {
+            println!("{ANSI_RED}✖ build failed{ANSI_RESET}");
+            for error in errors {
+                println!("  {error}");
+            }
+        }
+        TestSummary::Ran {
+            passed, failed: 0, ..
+        } => println!("{ANSI_GREEN}✔ examples: {passed} passed{ANSI_RESET}"),
+        TestSummary::Ran {
+            passed,
+            failed,
+            failures,
+        } => {
+            println!("{ANSI_RED}✖ examples: {passed} passed, {failed} failed{ANSI_RESET}");
+            for failure in failures {
+                println!("  {failure}");
+            }
+        }
+    }
+
+    if !tests.is_success() {
+        println!("{ANSI_ITALIC}skipping the solution{ANSI_RESET}");
+        return;
+    }
+
+    let day_arg = day.to_string();
+    let mut run_args = vec!["run", "--quiet", "--bin", &day_arg];
+    if !args.is_empty() {
+        run_args.push("--");
+        run_args.extend(args.iter().map(String::as_str));
+    }
+    match cargo(&run_args, release) {
+        Ok(output) if output.status.success() => {
+            for answer in parse_answers(&String::from_utf8_lossy(&output.stdout)) {
+                println!("  {answer}");
+            }
+        }
+        Ok(output) => {
+            println!(
+                "{ANSI_RED}✖ solution exited with {}{ANSI_RESET}",
+                output.status
+            );
+            // show the panic location and message rather than the backtrace hint.
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            stderr
+                .lines()
+                .skip_while(|l| !l.contains("panicked at"))
+                .take(2)
+                .for_each(|line| println!("  {line}"));
+        }
+        Err(e) => eprintln!("Failed to call cargo: {e}"),
+    }
+}
+
+/// Runs the day once, then again every time one of its files is saved, passing `args` to the
+/// solution binary. Never returns.
+pub fn watch(day: Day, release: bool, args: &[String]) {
+    println!("👀 Watching day {day}, press Ctrl-C to stop.");
+
+    let mut debouncer = Debouncer::new(DEBOUNCE_DELAY);
+    let mut last = snapshot(day);
+    let mut run = 1;
+    run_once(day, release, args, run);
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let current = snapshot(day);
+        let changed = current != last;
+        last = current;
+
+        if debouncer.poll(changed, Instant::now()) {
+            run += 1;
+            run_once(day, release, args, run);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{parse_answers, parse_test_summary, Debouncer, TestSummary};
+
+    #[test]
+    fn debounces_rapid_changes() {
+        let start = Instant::now();
+        let ms = |n| start + Duration::from_millis(n);
+        let mut debouncer = Debouncer::new(Duration::from_millis(300));
+
+        assert!(!debouncer.poll(false, ms(0)));
+        assert!(!debouncer.poll(true, ms(100)));
+        assert!(!debouncer.poll(true, ms(300)));
+        assert!(!debouncer.poll(false, ms(500)));
+        assert!(debouncer.poll(false, ms(600)));
+        // fires only once per burst.
+        assert!(!debouncer.poll(false, ms(900)));
+    }
+
+    #[test]
+    fn parses_passing_tests() {
+        let stdout = "running 2 tests\ntest tests::test_part_one ... ok\ntest tests::test_part_two ... ok\n\ntest result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s\n";
+        let summary = parse_test_summary(stdout, "");
+        assert_eq!(
+            summary,
+            TestSummary::Ran {
+                passed: 2,
+                failed: 0,
+                failures: vec![]
+            }
+        );
+        assert!(summary.is_success());
+    }
+
+    #[test]
+    fn parses_failing_tests() {
+        let stdout = "running 2 tests\ntest tests::test_part_one ... ok\ntest tests::test_part_two ... FAILED\n\ntest result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s\n";
+        let summary = parse_test_summary(stdout, "");
+        assert_eq!(
+            summary,
+            TestSummary::Ran {
+                passed: 1,
+                failed: 1,
+                failures: vec!["tests::test_part_two".into()]
+            }
+        );
+        assert!(!summary.is_success());
+    }
+
+    #[test]
+    fn parses_build_failures() {
+        let stderr = "   Compiling advent_of_code v0.11.0\nerror[E0425]: cannot find value `x` in this scope\n --> src/bin/01.rs:4:5\nerror: could not compile `advent_of_code`\n";
+        let summary = parse_test_summary("", stderr);
+        assert_eq!(
+            summary,
+            TestSummary::BuildFailed(vec![
+                "error[E0425]: cannot find value `x` in this scope".into(),
+                "error: could not compile `advent_of_code`".into()
+            ])
+        );
+    }
+
+    #[test]
+    fn parses_answers() {
+        let stdout = "Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.2ms)\nPart 2: ✖\rPart 2: ✖             \nnoise\n";
+        assert_eq!(
+            parse_answers(stdout),
+            vec!["Part 1: 42 (1.2ms)".to_string(), "Part 2: ✖".to_string()]
+        );
+    }
+}